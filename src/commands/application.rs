@@ -1,8 +1,15 @@
 use commands;
+use helpers;
+use input::KeyMap;
 use std::mem;
 use models::application::{Application, Mode};
-use models::application::modes::{insert, jump, line_jump, select, select_line, search_insert};
-use models::application::modes::{OpenMode, SymbolJumpMode};
+use models::application::modes::{insert, jump, line_jump, macros, register, select, select_line};
+use models::application::modes::search_insert;
+use models::application::modes::{replace_insert, save_as, undo_tree, unsaved_changes};
+use models::application::modes::{CommandMode, OpenMode, ProjectSearchMode, SymbolJumpMode};
+use models::application::modes::command::DisplayableCommand;
+use models::application::modes::buffer_list::{BufferListEntry, BufferListMode};
+use models::application::modes::unsaved_changes::PendingAction;
 
 pub fn switch_to_normal_mode(app: &mut Application) {
     commands::buffer::end_command_group(app);
@@ -45,10 +52,44 @@ pub fn switch_to_jump_mode(app: &mut Application) {
     };
 }
 
+/// Waits for a register name to be typed, keeping the current selection (if any).
+pub fn switch_to_register_mode(app: &mut Application) {
+    let old_mode = mem::replace(&mut app.mode, Mode::Register(register::new()));
+
+    match old_mode {
+        Mode::Select(select_mode) => {
+            if let Mode::Register(ref mut mode) = app.mode {
+                mode.select_mode = jump::SelectModeOptions::Select(select_mode);
+            }
+        }
+        Mode::SelectLine(select_mode) => {
+            if let Mode::Register(ref mut mode) = app.mode {
+                mode.select_mode = jump::SelectModeOptions::SelectLine(select_mode);
+            }
+        }
+        // Repeating the command (e.g. with a count) shouldn't discard the selection.
+        Mode::Register(register_mode) => app.mode = Mode::Register(register_mode),
+        _ => (),
+    }
+}
+
 pub fn switch_to_line_jump_mode(app: &mut Application) {
     app.mode = Mode::LineJump(line_jump::new());
 }
 
+/// Waits for the name of a macro to play. Switching again before it's been
+/// typed (e.g. when repeated using a count) plays it an additional time.
+pub fn switch_to_macro_mode(app: &mut Application) {
+    if let Mode::Macro(ref mut mode) = app.mode {
+        if mode.action == macros::MacroAction::Play {
+            mode.count += 1;
+            return;
+        }
+    }
+
+    app.mode = Mode::Macro(macros::new(macros::MacroAction::Play));
+}
+
 pub fn switch_to_open_mode(app: &mut Application) {
     app.mode = Mode::Open(OpenMode::new(app.workspace.path.clone()));
     commands::open_mode::search(app);
@@ -82,11 +123,115 @@ pub fn switch_to_select_line_mode(app: &mut Application) {
 }
 
 pub fn switch_to_search_insert_mode(app: &mut Application) {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        // Track where we started, so that we can return if the search is cancelled.
+        let line_offset = app.view.visible_region(buffer).line_offset();
+        app.mode = Mode::SearchInsert(search_insert::new(app.search_regex,
+                                                         *buffer.cursor,
+                                                         line_offset));
+    }
+}
+
+pub fn switch_to_replace_insert_mode(app: &mut Application) {
     if app.workspace.current_buffer().is_some() {
-        app.mode = Mode::SearchInsert(search_insert::new());
+        // Start with the last search query, since it's likely what we're replacing.
+        let query = app.search_query.clone().unwrap_or(String::new());
+        app.mode = Mode::ReplaceInsert(replace_insert::new(query, app.search_regex));
+    }
+}
+
+pub fn switch_to_project_search_mode(app: &mut Application) {
+    app.mode = Mode::ProjectSearch(ProjectSearchMode::new(app.workspace.path.clone(),
+                                                          app.search_regex));
+}
+
+pub fn switch_to_project_replace_insert_mode(app: &mut Application) {
+    // Start with the last search query, since it's likely what we're replacing.
+    let query = app.search_query.clone().unwrap_or(String::new());
+    app.mode = Mode::ProjectReplaceInsert(replace_insert::new(query, app.search_regex));
+}
+
+pub fn switch_to_save_as_mode(app: &mut Application) {
+    // Start with the buffer's current path, if it has one.
+    let path = match app.workspace.current_buffer() {
+        Some(buffer) => buffer.path.as_ref().map(|path| path.to_string_lossy().into_owned()),
+        None => return,
+    };
+
+    app.mode = Mode::SaveAs(save_as::new(path.unwrap_or(String::new())));
+}
+
+pub fn switch_to_command_mode(app: &mut Application) {
+    // Pair each command with its normal mode key binding, for reference.
+    let displayable_commands = commands::hash_map()
+                                   .keys()
+                                   .filter(|&&name| runs_from_the_palette(&app.key_map, name))
+                                   .map(|&name| {
+                                       DisplayableCommand {
+                                           name: name,
+                                           binding: app.key_map.binding_for("normal", name),
+                                       }
+                                   })
+                                   .collect();
+
+    app.mode = Mode::Command(CommandMode::new(displayable_commands));
+    commands::command_mode::search(app);
+}
+
+/// Whether the command does anything when run from the command palette, which
+/// switches back to normal mode first. Commands bound only in other modes act
+/// on those modes alone, other than those switching modes, which work anywhere.
+fn runs_from_the_palette(key_map: &KeyMap, name: &str) -> bool {
+    if commands::INTERNAL_COMMANDS.contains(&name) {
+        return false;
+    }
+    let modes = key_map.modes_binding(name);
+
+    modes.is_empty() || modes.contains(&"normal") || name.contains("::switch_to_")
+}
+
+pub fn switch_to_buffer_list_mode(app: &mut Application) {
+    let entries = buffer_entries(app);
+    app.mode = Mode::BufferList(BufferListMode::new(entries));
+    commands::buffer_list::search(app);
+}
+
+pub fn switch_to_undo_tree_mode(app: &mut Application) {
+    // Make sure the list includes the buffer's latest changes.
+    commands::undo_tree::record(app);
+
+    let mode = match app.workspace.current_buffer().and_then(|buffer| buffer.id) {
+        Some(id) => app.undo_trees.get(&id).map(|tree| undo_tree::new(tree)),
+        None => None,
+    };
+    if let Some(mode) = mode {
+        app.mode = Mode::UndoTree(mode);
     }
 }
 
+/// Exits the application, asking what to do about unsaved changes first, if any.
 pub fn exit(app: &mut Application) {
-    app.mode = Mode::Exit;
+    let modified_entries: Vec<BufferListEntry> = buffer_entries(app)
+                                                     .into_iter()
+                                                     .filter(|entry| entry.modified)
+                                                     .collect();
+
+    if modified_entries.is_empty() {
+        app.mode = Mode::Exit;
+    } else {
+        app.mode = Mode::UnsavedChanges(unsaved_changes::new(PendingAction::Exit,
+                                                             modified_entries));
+    }
+}
+
+/// Describes each of the workspace's buffers.
+fn buffer_entries(app: &mut Application) -> Vec<BufferListEntry> {
+    let mut entries = Vec::new();
+    helpers::workspace::each_buffer(&mut app.workspace, |buffer| {
+        if let Some(entry) = BufferListEntry::new(buffer) {
+            entries.push(entry);
+        }
+    });
+
+    entries
 }
@@ -1,26 +1,171 @@
 extern crate scribe;
 
 use commands;
+use std::collections::HashMap;
+use std::fs;
 use std::mem;
+use std::path::PathBuf;
+use helpers;
+use helpers::indentation::{self, Indentation};
 use helpers::token::{Direction, adjacent_token_position};
-use models::application::{Application, ClipboardContent, Mode};
+use models::application::{Application, ClipboardContent, Edit, LastPaste, Message, Mode};
+use models::application::Preferences;
+use models::application::modes::buffer_list::BufferListEntry;
+use models::application::modes::unsaved_changes::{self, PendingAction};
+use scribe::Buffer;
 use scribe::buffer::{Position, Range};
 
-pub fn save(app: &mut Application) {
-    remove_trailing_whitespace(app);
-    ensure_trailing_newline(app);
-    match app.workspace.current_buffer() {
-        Some(buffer) => buffer.save(),
+/// Opens the file at the path, or switches to its buffer if it's already open.
+/// Newly opened files have their on-disk state recorded, so that changes made
+/// to them outside of the application from then on can be detected, and their
+/// undo trees started.
+pub fn open_file(app: &mut Application, path: PathBuf) {
+    let mut open_ids = Vec::new();
+    helpers::workspace::each_buffer(&mut app.workspace, |buffer| open_ids.extend(buffer.id));
+    app.workspace.open_buffer(path);
+
+    let opened_path = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            if buffer.id.map(|id| open_ids.contains(&id)).unwrap_or(true) {
+                None
+            } else {
+                buffer.path.clone()
+            }
+        }
         None => None,
     };
+    if let Some(path) = opened_path {
+        app.disk_states.record(&path);
+        detect_indentation(app);
+        commands::undo_tree::start(app);
+    }
 }
 
-pub fn delete(app: &mut Application) {
-    match app.workspace.current_buffer() {
-        Some(buffer) => buffer.delete(),
-        None => (),
+/// Detects the indentation used by the current buffer's content, remembering
+/// it so that it doesn't have to be worked out again each time it's needed.
+pub fn detect_indentation(app: &mut Application) {
+    let detected = match app.workspace.current_buffer() {
+        Some(buffer) => buffer.id.map(|id| (id, indentation::detect(&buffer.data()))),
+        None => None,
+    };
+    if let Some((id, detected_indentation)) = detected {
+        app.indentations.insert(id, detected_indentation);
+    }
+}
+
+/// Saves the current buffer, asking for a path if it doesn't have one, or
+/// what to do about its file if that's been changed outside of the application.
+pub fn save(app: &mut Application) {
+    if let Err(error) = write(app) {
+        app.messages.push(Message::error(error));
+    }
+}
+
+/// Saves the current buffer as save does, returning an
+/// explanation if it couldn't be written to its file.
+pub fn write(app: &mut Application) -> Result<(), String> {
+    run_save_hooks(app);
+
+    // Buffers without a path need one before they can be saved.
+    let path = match app.workspace.current_buffer() {
+        Some(buffer) => buffer.path.clone(),
+        None => return Ok(()),
+    };
+    let path = match path {
+        Some(path) => path,
+        None => {
+            commands::application::switch_to_save_as_mode(app);
+            return Ok(());
+        }
+    };
+
+    // Don't overwrite changes made outside of the application
+    // without asking what to do about them, first.
+    commands::external_change::check(app);
+    if let Mode::ExternalChange(_) = app.mode {
+        return Ok(());
+    }
+
+    match app.workspace.current_buffer().and_then(|buffer| buffer.save()) {
+        Some(error) => Err(format!("Couldn't save {}: {}", path.to_string_lossy(), error)),
+        None => {
+            app.disk_states.record(&path);
+            commands::swap::remove(app);
+            commands::undo_tree::save(app);
+            app.messages.push(Message::info(format!("Saved {}", path.to_string_lossy())));
+
+            Ok(())
+        }
+    }
+}
+
+/// Cleans up the buffer before it's saved, as configured in the preferences.
+pub fn run_save_hooks(app: &mut Application) {
+    let path = match app.workspace.current_buffer() {
+        Some(buffer) => buffer.path.clone(),
+        None => return,
+    };
+
+    if app.preferences.trim_trailing_whitespace(path.as_ref()) {
+        remove_trailing_whitespace(app);
     }
+    if app.preferences.ensure_trailing_newline(path.as_ref()) {
+        ensure_trailing_newline(app);
+    }
+}
+
+/// Saves the buffer, creating any missing directories in its path first.
+pub fn create_parent_directories_and_save(app: &mut Application) {
+    if let Some(buffer) = app.workspace.current_buffer() {
+        if let Some(parent) = buffer.path.as_ref().and_then(|path| path.parent()) {
+            if let Err(error) = fs::create_dir_all(parent) {
+                app.messages.push(Message::error(format!("Couldn't create {}: {}",
+                                                         parent.to_string_lossy(),
+                                                         error)));
+                return;
+            }
+        }
+    }
+
+    save(app);
+}
+
+pub fn delete(app: &mut Application) {
+    commands::multi_cursor::at_each(app, delete_at_cursor);
+}
+
+fn delete_at_cursor(app: &mut Application) -> Option<Edit> {
+    let edit = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            // The character at the cursor is deleted, or the
+            // newline that ends its line, if there isn't one.
+            let position = *buffer.cursor;
+            let next_position = Position {
+                line: position.line,
+                offset: position.offset + 1,
+            };
+            let old_end = match buffer.read(&Range::new(position, next_position)) {
+                Some(_) => next_position,
+                None => {
+                    Position {
+                        line: position.line + 1,
+                        offset: 0,
+                    }
+                }
+            };
+            buffer.delete();
+
+            Some(Edit {
+                old_end: old_end,
+                new_end: position,
+            })
+        }
+        None => None,
+    };
+    mark_changed(app);
     commands::view::scroll_to_cursor(app);
+
+    edit
 }
 
 pub fn delete_token(app: &mut Application) {
@@ -113,22 +258,56 @@ pub fn merge_next_line(app: &mut Application) {
             });
             buffer.insert(&merged_lines);
             buffer.cursor.move_to(target_position);
-            buffer.end_operation_group();
         }
         None => (),
     }
+    mark_changed(app);
+    end_command_group(app);
 }
 
+/// Closes the current buffer, asking what to do about its unsaved changes first, if any.
 pub fn close(app: &mut Application) {
-    // Clean up view-related data for the buffer.
+    let modified_entry = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            if buffer.modified() {
+                BufferListEntry::new(buffer)
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+
+    match modified_entry {
+        Some(entry) => {
+            app.mode = Mode::UnsavedChanges(unsaved_changes::new(PendingAction::CloseBuffer,
+                                                                 vec![entry]));
+        }
+        None => close_without_saving(app),
+    }
+}
+
+/// Closes the current buffer, discarding any unsaved changes.
+pub fn close_without_saving(app: &mut Application) {
+    commands::swap::remove(app);
+
+    // Clean up view-related data and history for the buffer.
     if let Some(buf) = app.workspace.current_buffer() {
         app.view.forget_buffer(&buf);
+        if let Some(id) = buf.id {
+            app.undo_trees.remove(&id);
+        }
     }
 
     app.workspace.close_current_buffer();
 }
 
 pub fn backspace(app: &mut Application) {
+    commands::multi_cursor::at_each(app, backspace_at_cursor);
+}
+
+fn backspace_at_cursor(app: &mut Application) -> Option<Edit> {
+    let original_position = app.workspace.current_buffer().map(|buffer| *buffer.cursor);
     let outdent = match app.workspace.current_buffer() {
         Some(buffer) => {
             if buffer.cursor.offset == 0 {
@@ -155,39 +334,60 @@ pub fn backspace(app: &mut Application) {
         }
         None => false,
     };
+    mark_changed(app);
 
     if outdent {
         commands::buffer::outdent_line(app);
     }
 
     commands::view::scroll_to_cursor(app);
+
+    // Whatever was removed came before the cursor.
+    edit_to_cursor(app, original_position)
 }
 
 pub fn insert_char(app: &mut Application) {
-    match app.workspace.current_buffer() {
+    commands::multi_cursor::at_each(app, insert_char_at_cursor);
+}
+
+fn insert_char_at_cursor(app: &mut Application) -> Option<Edit> {
+    let edit = match app.workspace.current_buffer() {
         Some(buffer) => {
             match app.mode {
                 Mode::Insert(ref mut insert_mode) => {
                     match insert_mode.input {
                         Some(input) => {
-                            buffer.insert(&input.to_string());
+                            let content = input.to_string();
+                            let edit = Edit::insertion(*buffer.cursor, &content);
+                            buffer.insert(&content);
                             buffer.cursor.move_right();
+
+                            Some(edit)
                         }
-                        None => (),
+                        None => None,
                     }
                 }
-                _ => (),
+                _ => None,
             }
         }
-        None => (),
-    }
+        None => None,
+    };
+    mark_changed(app);
     commands::view::scroll_to_cursor(app);
+
+    edit
 }
 
 /// Inserts a newline character at the current cursor position.
 /// Also performs automatic indentation, basing the indent off
-/// of the previous line's leading whitespace.
+/// of the previous line's leading whitespace, expressed using
+/// the buffer's indentation.
 pub fn insert_newline(app: &mut Application) {
+    commands::multi_cursor::at_each(app, insert_newline_at_cursor);
+}
+
+fn insert_newline_at_cursor(app: &mut Application) -> Option<Edit> {
+    let original_position = app.workspace.current_buffer().map(|buffer| *buffer.cursor);
     match app.workspace.current_buffer() {
         Some(buffer) => {
             // Insert the newline character.
@@ -201,9 +401,16 @@ pub fn insert_newline(app: &mut Application) {
             // Get the previous line.
             match buffer.data().lines().nth(position.line) {
                 Some(line) => {
-                    // Get the whitespace from the start of
-                    // the previous line and add it to the new line.
-                    let prefix: String = line.chars().take_while(|&c| c.is_whitespace()).collect();
+                    // Get the whitespace from the start of the previous line and
+                    // add it to the new line, using the buffer's indentation.
+                    let whitespace: String = line.chars()
+                                                 .take_while(|&c| c.is_whitespace())
+                                                 .collect();
+                    let prefix = buffer_indentation(buffer,
+                                                    &mut app.indentations,
+                                                    &app.preferences)
+                                     .convert(&whitespace,
+                                              app.preferences.tab_width(buffer.path.as_ref()));
                     buffer.insert(&prefix);
 
                     // Move the cursor to the end of the inserted whitespace.
@@ -218,14 +425,34 @@ pub fn insert_newline(app: &mut Application) {
         }
         None => (),
     }
+    mark_changed(app);
     commands::view::scroll_to_cursor(app);
+
+    edit_to_cursor(app, original_position)
+}
+
+/// An edit that replaced the content up to the original cursor
+/// position, leaving the cursor at the end of its replacement.
+fn edit_to_cursor(app: &mut Application, original_position: Option<Position>) -> Option<Edit> {
+    match (original_position, app.workspace.current_buffer()) {
+        (Some(position), Some(buffer)) => {
+            Some(Edit {
+                old_end: position,
+                new_end: *buffer.cursor,
+            })
+        }
+        _ => None,
+    }
 }
 
 pub fn indent_line(app: &mut Application) {
+    start_command_group(app);
     match app.workspace.current_buffer() {
         Some(buffer) => {
-            // FIXME: Determine this based on file type and/or user config.
-            let tab_content = "  ";
+            let tab_content = buffer_indentation(buffer,
+                                                 &mut app.indentations,
+                                                 &app.preferences)
+                                  .unit();
 
             let target_position = match app.mode {
                 Mode::Insert(_) => {
@@ -250,30 +477,32 @@ pub fn indent_line(app: &mut Application) {
                 _ => buffer.cursor.line..buffer.cursor.line + 1,
             };
 
-            // Move to the start of the current line and
-            // insert the content, as a single operation.
-            buffer.start_operation_group();
+            // Move to the start of the current line and insert the content.
             for line in lines {
                 buffer.cursor.move_to(Position {
                     line: line,
                     offset: 0,
                 });
-                buffer.insert(tab_content);
+                buffer.insert(&tab_content);
             }
-            buffer.end_operation_group();
 
             // Move to the original position, shifted to compensate for the indent.
             buffer.cursor.move_to(target_position);
         }
         None => (),
     }
+    mark_changed(app);
+    end_command_group(app);
 }
 
 pub fn outdent_line(app: &mut Application) {
+    // Group the individual outdent operations as one.
+    start_command_group(app);
     match app.workspace.current_buffer() {
         Some(buffer) => {
-            // FIXME: Determine this based on file type and/or user config.
-            let tab_content = "  ";
+            let indentation = buffer_indentation(buffer,
+                                                 &mut app.indentations,
+                                                 &app.preferences);
             let data = buffer.data();
 
             // Get the range of lines we'll outdent based on
@@ -289,42 +518,29 @@ pub fn outdent_line(app: &mut Application) {
                 _ => buffer.cursor.line..buffer.cursor.line + 1,
             };
 
-            // Group the individual outdent operations as one.
-            buffer.start_operation_group();
-
             for line in lines {
                 let line_content = data.lines().nth(line);
 
                 match line_content {
                     Some(content) => {
-                        let mut space_char_count = 0;
-
-                        // Check for leading whitespace.
-                        for character in content.chars().take(tab_content.len()) {
-                            if character == ' ' {
-                                space_char_count += 1;
-                            } else {
-                                // We've run into a non-whitespace character; stop here.
-                                break;
-                            }
-                        }
+                        let indent_length = indentation.outdent_length(content);
 
                         // Remove leading whitespace, up to indent size,
                         // if we found any, and adjust cursor accordingly.
-                        if space_char_count > 0 {
+                        if indent_length > 0 {
                             buffer.delete_range(Range::new(Position {
                                                                line: line,
                                                                offset: 0,
                                                            },
                                                            Position {
                                                                line: line,
-                                                               offset: space_char_count,
+                                                               offset: indent_length,
                                                            }));
 
                             // Figure out where the cursor should sit, guarding against underflow.
                             let target_offset = match buffer.cursor
                                                             .offset
-                                                            .checked_sub(space_char_count) {
+                                                            .checked_sub(indent_length) {
                                 Some(offset) => offset,
                                 None => 0,
                             };
@@ -339,12 +555,29 @@ pub fn outdent_line(app: &mut Application) {
                     None => (),
                 }
             }
-
-            // Finish grouping the individual outdent operations as one.
-            buffer.end_operation_group();
         }
         None => (),
     }
+
+    // Finish grouping the individual outdent operations as one.
+    mark_changed(app);
+    end_command_group(app);
+}
+
+/// The indentation to use in the buffer: whatever was detected in its content when
+/// it was opened (which is done now if it hasn't been), or the preferred indentation.
+fn buffer_indentation(buffer: &Buffer,
+                      indentations: &mut HashMap<usize, Option<Indentation>>,
+                      preferences: &Preferences)
+                      -> Indentation {
+    let detected_indentation = match buffer.id {
+        Some(id) => {
+            *indentations.entry(id).or_insert_with(|| indentation::detect(&buffer.data()))
+        }
+        None => None,
+    };
+
+    detected_indentation.unwrap_or_else(|| preferences.indentation(buffer.path.as_ref()))
 }
 
 pub fn change_token(app: &mut Application) {
@@ -371,6 +604,7 @@ pub fn delete_rest_of_line(app: &mut Application) {
         }
         None => (),
     }
+    mark_changed(app);
 }
 
 pub fn change_rest_of_line(app: &mut Application) {
@@ -379,33 +613,55 @@ pub fn change_rest_of_line(app: &mut Application) {
 }
 
 pub fn start_command_group(app: &mut Application) {
+    // The group's changes are recorded in the buffer's
+    // undo tree once it's closed; make sure it has one.
+    commands::undo_tree::start(app);
+
     match app.workspace.current_buffer() {
         Some(buffer) => buffer.start_operation_group(),
         None => (),
     }
+    app.command_group_open = true;
 }
 
 pub fn end_command_group(app: &mut Application) {
+    // Repeated commands are grouped as a whole; leave that group open.
+    if app.repeating {
+        return;
+    }
+
     match app.workspace.current_buffer() {
         Some(buffer) => buffer.end_operation_group(),
         None => (),
     }
+    app.command_group_open = false;
 }
 
-pub fn undo(app: &mut Application) {
-    match app.workspace.current_buffer() {
-        Some(buffer) => buffer.undo(),
-        None => (),
+/// Notes that the current buffer has been changed, so that anything based
+/// on its content can tell that it's out of date. Commands that change a
+/// buffer's content are expected to call this once they've done so.
+pub fn mark_changed(app: &mut Application) {
+    let id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+    if let Some(id) = id {
+        app.revisions.increment(id);
     }
-    commands::view::scroll_to_cursor(app);
+}
+
+/// The current buffer's ID, and its revision: a number
+/// that changes whenever the buffer's content does.
+pub fn revision(app: &mut Application) -> Option<(usize, usize)> {
+    let id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+    let revisions = &app.revisions;
+
+    id.map(|id| (id, revisions.get(id)))
+}
+
+pub fn undo(app: &mut Application) {
+    commands::undo_tree::undo(app);
 }
 
 pub fn redo(app: &mut Application) {
-    match app.workspace.current_buffer() {
-        Some(buffer) => buffer.redo(),
-        None => (),
-    }
-    commands::view::scroll_to_cursor(app);
+    commands::undo_tree::redo(app);
 }
 
 pub fn paste(app: &mut Application) {
@@ -417,12 +673,78 @@ pub fn paste(app: &mut Application) {
         _ => true,
     };
 
+    // Cycling through the clipboard history after pasting from a named
+    // register starts with the newest entry, rather than skipping it.
+    let next_index = if app.clipboard.register_selected() {
+        0
+    } else {
+        1
+    };
+    let content = app.clipboard.get_content().clone();
+    commands::multi_cursor::at_each(app, |app| {
+        paste_content(app, &content, insert_below, next_index)
+    });
+
+    // Pastes made at several cursors can't be cycled through.
+    let revision = revision(app);
+    if !app.cursors.positions(revision).is_empty() {
+        app.last_paste = None;
+    }
+}
+
+/// Replaces the content that was just pasted with the next (older) entry in
+/// the clipboard history, wrapping around to the newest one after the oldest.
+/// Does nothing if the buffer has been changed since pasting.
+pub fn cycle_paste(app: &mut Application) {
+    let (cursor, insert_below, next_index) = match app.last_paste {
+        Some(ref last_paste) => {
+            (last_paste.cursor, last_paste.insert_below, last_paste.next_index)
+        }
+        None => return,
+    };
+    let history_length = app.clipboard.history().len();
+    if history_length == 0 {
+        return;
+    }
+    let index = next_index % history_length;
+    let content = app.clipboard.history()[index].clone();
+
     match app.workspace.current_buffer() {
         Some(buffer) => {
-            match app.clipboard.get_content() {
-                &ClipboardContent::Inline(ref content) => buffer.insert(content),
-                &ClipboardContent::Block(ref content) => {
-                    let original_cursor_position = *buffer.cursor.clone();
+            let unchanged = match app.last_paste {
+                Some(ref last_paste) => last_paste.data == buffer.data(),
+                None => false,
+            };
+            if !unchanged {
+                app.last_paste = None;
+                return;
+            }
+
+            // Take back the previous paste.
+            buffer.undo();
+            buffer.cursor.move_to(cursor);
+        }
+        None => return,
+    }
+
+    paste_content(app, &content, insert_below, index + 1);
+}
+
+/// Inserts the content at the cursor, or on the line below it for
+/// block content, if specified, remembering the paste so that it can
+/// be cycled through the clipboard history afterwards.
+fn paste_content(app: &mut Application,
+                 content: &ClipboardContent,
+                 insert_below: bool,
+                 next_index: usize)
+                 -> Option<Edit> {
+    let edit = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            let original_cursor_position = *buffer.cursor.clone();
+
+            let edit = match *content {
+                ClipboardContent::Inline(ref content) => insert_content(buffer, content),
+                ClipboardContent::Block(ref content) => {
                     let line = original_cursor_position.line;
 
                     if insert_below {
@@ -440,28 +762,51 @@ pub fn paste(app: &mut Application) {
                                         line: line,
                                         offset: line_content.len(),
                                     });
-                                    buffer.insert(&format!("\n{}", content));
+                                    let edit = insert_content(buffer,
+                                                              &format!("\n{}", content));
                                     buffer.cursor.move_to(original_cursor_position);
+
+                                    edit
                                 }
                                 None => {
                                     // We're on a trailing newline, which doesn't
                                     // have any data; just insert the content here.
-                                    buffer.insert(content);
+                                    insert_content(buffer, content)
                                 }
                             }
                         } else {
-                            buffer.insert(content);
+                            insert_content(buffer, content)
                         }
                     } else {
-                        buffer.insert(content);
+                        insert_content(buffer, content)
                     }
                 }
-                &ClipboardContent::None => (),
-            }
+                ClipboardContent::None => return None,
+            };
+
+            app.last_paste = Some(LastPaste {
+                data: buffer.data(),
+                cursor: original_cursor_position,
+                insert_below: insert_below,
+                next_index: next_index,
+            });
+
+            Some(edit)
         }
-        None => (),
-    }
+        None => None,
+    };
+    mark_changed(app);
     commands::view::scroll_to_cursor(app);
+
+    edit
+}
+
+/// Inserts the content at the cursor, returning the edit it amounts to.
+fn insert_content(buffer: &mut Buffer, content: &str) -> Edit {
+    let edit = Edit::insertion(*buffer.cursor, content);
+    buffer.insert(content);
+
+    edit
 }
 
 pub fn paste_above(app: &mut Application) {
@@ -485,6 +830,7 @@ pub fn paste_above(app: &mut Application) {
         }
         None => (),
     }
+    mark_changed(app);
 }
 
 pub fn remove_trailing_whitespace(app: &mut Application) {
@@ -550,6 +896,7 @@ pub fn remove_trailing_whitespace(app: &mut Application) {
         }
         None => (),
     }
+    mark_changed(app);
 }
 
 pub fn ensure_trailing_newline(app: &mut Application) {
@@ -557,7 +904,9 @@ pub fn ensure_trailing_newline(app: &mut Application) {
     match app.workspace.current_buffer() {
         Some(buffer) => {
             let data = buffer.data();
-            if data.chars().last().unwrap() != '\n' {
+
+            // Leave empty buffers alone; there's no line to terminate.
+            if data.chars().last().map(|c| c != '\n').unwrap_or(false) {
                 match buffer.data().lines().enumerate().last() {
                     Some((line_no, line)) => {
                         let original_position = *buffer.cursor;
@@ -577,6 +926,7 @@ pub fn ensure_trailing_newline(app: &mut Application) {
         }
         None => (),
     }
+    mark_changed(app);
 }
 
 #[cfg(test)]
@@ -584,7 +934,10 @@ mod tests {
     extern crate scribe;
 
     use commands;
-    use models::application::ClipboardContent;
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Read;
+    use models::application::{ClipboardContent, MessageKind, Mode};
     use scribe::Buffer;
     use scribe::buffer::Position;
 
@@ -835,6 +1188,47 @@ mod tests {
                    "  amp\n  editor");
     }
 
+    #[test]
+    fn indent_line_uses_the_buffer_indentation() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n\teditor\n");
+
+        // Now that we've set up the buffer, add it
+        // to the application and call the command.
+        app.workspace.add_buffer(buffer);
+        super::indent_line(&mut app);
+
+        // Ensure that the buffer's tab indentation is used.
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "\tamp\n\teditor\n");
+    }
+
+    #[test]
+    fn outdent_line_removes_a_tab_from_tab_indented_buffers() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n\t\teditor\n");
+        buffer.cursor.move_to(Position {
+            line: 1,
+            offset: 3,
+        });
+
+        // Now that we've set up the buffer, add it
+        // to the application and call the command.
+        app.workspace.add_buffer(buffer);
+        super::outdent_line(&mut app);
+
+        // Ensure that a single tab is removed, and the cursor is updated.
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "amp\n\teditor\n");
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 1,
+                       offset: 2,
+                   });
+    }
+
     #[test]
     fn outdent_line_removes_two_spaces_from_start_of_line() {
         let mut app = ::models::application::new();
@@ -993,6 +1387,30 @@ mod tests {
                    "amp\neditor\n");
     }
 
+    #[test]
+    fn save_prompts_for_a_path_when_the_buffer_has_none() {
+        let mut app = ::models::application::new();
+        app.workspace.add_buffer(Buffer::new());
+        super::save(&mut app);
+
+        assert!(match app.mode {
+            Mode::SaveAs(_) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn save_reports_errors() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.path = Some(env::temp_dir().join("amp_missing_directory/save.txt"));
+        app.workspace.add_buffer(buffer);
+        super::save(&mut app);
+
+        assert_eq!(app.messages.len(), 1);
+        assert_eq!(app.messages[0].kind, MessageKind::Error);
+    }
+
     #[test]
     fn paste_inserts_at_cursor_when_pasting_inline_data() {
         let mut app = ::models::application::new();
@@ -1232,12 +1650,43 @@ mod tests {
                    "amp\neditor\n");
     }
 
+    #[test]
+    fn ensure_trailing_newline_does_nothing_for_empty_buffers() {
+        let mut app = ::models::application::new();
+        app.workspace.add_buffer(Buffer::new());
+        commands::buffer::ensure_trailing_newline(&mut app);
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "");
+    }
+
+    #[test]
+    fn create_parent_directories_and_save_writes_to_non_existent_directories() {
+        let directory = env::temp_dir().join("amp_create_parent_directories_test");
+        let path = directory.join("nested").join("file.txt");
+        let _ = fs::remove_dir_all(&directory);
+
+        // Set up a new buffer for a path that doesn't exist yet.
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        buffer.path = Some(path.clone());
+        app.workspace.add_buffer(buffer);
+        commands::buffer::create_parent_directories_and_save(&mut app);
+
+        // Ensure that the file was written.
+        let mut data = String::new();
+        File::open(&path).unwrap().read_to_string(&mut data).unwrap();
+        assert_eq!(data, "amp\n");
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
     #[test]
     fn paste_with_inline_content_replaces_selection() {
         let mut app = ::models::application::new();
         let mut buffer = Buffer::new();
         buffer.insert("amp");
-        app.clipboard.set_content(ClipboardContent::Inline("editor".to_string()));
+        app.clipboard.set_content(ClipboardContent::Inline("editor".to_string())).unwrap();
 
         // Now that we've set up the buffer, add it to
         // the application, select its contents, and paste.
@@ -1259,7 +1708,7 @@ mod tests {
         let mut app = ::models::application::new();
         let mut buffer = Buffer::new();
         buffer.insert("amp\neditor");
-        app.clipboard.set_content(ClipboardContent::Block("paste amp\n".to_string()));
+        app.clipboard.set_content(ClipboardContent::Block("paste amp\n".to_string())).unwrap();
 
         // Now that we've set up the buffer, add it to
         // the application, select its contents, and paste.
@@ -1276,6 +1725,26 @@ mod tests {
         // assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp");
     }
 
+    #[test]
+    fn cycle_paste_replaces_the_paste_with_older_clipboard_content() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+        app.clipboard.set_content(ClipboardContent::Inline("editor ".to_string())).unwrap();
+        app.clipboard.set_content(ClipboardContent::Inline("text ".to_string())).unwrap();
+
+        super::paste(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "text amp");
+
+        super::cycle_paste(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "editor amp");
+
+        // Cycling wraps around to the newest content.
+        super::cycle_paste(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "text amp");
+    }
+
     #[test]
     fn paste_above_inserts_clipboard_contents_on_a_new_line_above() {
         let mut app = ::models::application::new();
@@ -1286,7 +1755,7 @@ mod tests {
         };
         buffer.insert("editor");
         buffer.cursor.move_to(original_position.clone());
-        app.clipboard.set_content(ClipboardContent::Block("amp\n".to_string()));
+        app.clipboard.set_content(ClipboardContent::Block("amp\n".to_string())).unwrap();
 
         // Now that we've set up the buffer,
         // add it to the application and paste.
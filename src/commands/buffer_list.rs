@@ -0,0 +1,113 @@
+use commands;
+use helpers;
+use models::application::{Application, Mode};
+
+/// Makes the selected buffer the current one.
+pub fn open_selected_buffer(app: &mut Application) {
+    let id = match app.mode {
+        Mode::BufferList(ref mode) => mode.selected_buffer_id(),
+        _ => None,
+    };
+
+    if let Some(id) = id {
+        helpers::workspace::select_buffer(&mut app.workspace, id);
+    }
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Returns to the buffer that was current before this one.
+pub fn open_previous_buffer(app: &mut Application) {
+    commands::workspace::switch_to_previous_buffer(app);
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Closes the selected buffer, leaving the current one (if
+/// it's not the one being closed) and the list in place.
+pub fn close_selected_buffer(app: &mut Application) {
+    let id = match app.mode {
+        Mode::BufferList(ref mode) => mode.selected_buffer_id(),
+        _ => None,
+    };
+    let id = match id {
+        Some(id) => id,
+        None => return,
+    };
+    let current_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+
+    if helpers::workspace::select_buffer(&mut app.workspace, id) {
+        commands::buffer::close(app);
+
+        // Leave the buffer current if we're asked about its unsaved changes.
+        if let Mode::UnsavedChanges(_) = app.mode {
+            return;
+        }
+
+        if let Some(current_id) = current_id {
+            helpers::workspace::select_buffer(&mut app.workspace, current_id);
+        }
+    }
+
+    // Rebuild the list without the closed buffer, keeping the query.
+    let input = match app.mode {
+        Mode::BufferList(ref mode) => mode.input.clone(),
+        _ => String::new(),
+    };
+    commands::application::switch_to_buffer_list_mode(app);
+    if let Mode::BufferList(ref mut mode) = app.mode {
+        mode.input = input;
+        mode.search();
+    }
+}
+
+pub fn search(app: &mut Application) {
+    match app.mode {
+        Mode::BufferList(ref mut mode) => mode.search(),
+        _ => (),
+    }
+}
+
+pub fn select_next_buffer(app: &mut Application) {
+    match app.mode {
+        Mode::BufferList(ref mut mode) => mode.results.select_next(),
+        _ => (),
+    }
+}
+
+pub fn select_previous_buffer(app: &mut Application) {
+    match app.mode {
+        Mode::BufferList(ref mut mode) => mode.results.select_previous(),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use models::application::Mode;
+    use scribe::Buffer;
+
+    #[test]
+    fn close_selected_buffer_keeps_the_current_buffer_and_updates_the_list() {
+        let mut app = ::models::application::new();
+        app.workspace.add_buffer(Buffer::new());
+        app.workspace.add_buffer(Buffer::new());
+        let current_id = app.workspace.current_buffer().unwrap().id;
+
+        // Close whichever buffer isn't current.
+        commands::application::switch_to_buffer_list_mode(&mut app);
+        if let Mode::BufferList(ref mut mode) = app.mode {
+            if mode.selected_buffer_id() == current_id {
+                mode.results.select_next();
+            }
+        }
+        super::close_selected_buffer(&mut app);
+
+        assert_eq!(app.workspace.current_buffer().unwrap().id, current_id);
+        assert!(match app.mode {
+            Mode::BufferList(ref mode) => mode.results.len() == 1,
+            _ => false,
+        });
+    }
+}
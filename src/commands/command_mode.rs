@@ -0,0 +1,113 @@
+use commands;
+use models::application::{Application, Mode};
+
+pub fn run(app: &mut Application) {
+    let name = match app.mode {
+        Mode::Command(ref mode) => mode.selected_command(),
+        _ => None,
+    };
+
+    // Leave command mode before running the command, so
+    // that it's free to switch to another mode of its own.
+    commands::application::switch_to_normal_mode(app);
+
+    if let Some(command) = name.and_then(|name| commands::hash_map().get(name).map(|c| *c)) {
+        command(app);
+    }
+}
+
+pub fn search(app: &mut Application) {
+    match app.mode {
+        Mode::Command(ref mut mode) => mode.search(),
+        _ => (),
+    }
+}
+
+pub fn select_next_command(app: &mut Application) {
+    match app.mode {
+        Mode::Command(ref mut mode) => mode.results.select_next(),
+        _ => (),
+    }
+}
+
+pub fn select_previous_command(app: &mut Application) {
+    match app.mode {
+        Mode::Command(ref mut mode) => mode.results.select_previous(),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+    use models::application::Mode;
+
+    #[test]
+    fn run_switches_to_normal_mode_and_runs_the_selected_command() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        // Find the command we're looking for and run it.
+        commands::application::switch_to_command_mode(&mut app);
+        match app.mode {
+            Mode::Command(ref mut mode) => mode.input = "cursor move_down".to_string(),
+            _ => (),
+        };
+        commands::command_mode::search(&mut app);
+        commands::command_mode::run(&mut app);
+
+        // Ensure that the command ran.
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 1,
+                       offset: 0,
+                   });
+
+        // Ensure that we're in normal mode.
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn the_palette_leaves_out_commands_that_only_act_in_other_modes() {
+        let mut app = ::models::application::new();
+        commands::application::switch_to_command_mode(&mut app);
+        let names: Vec<&str> = match app.mode {
+            Mode::Command(ref mode) => mode.commands.iter().map(|command| command.name).collect(),
+            _ => panic!("not in command mode"),
+        };
+
+        for name in vec!["buffer::save", "undo_tree::undo", "application::switch_to_normal_mode"] {
+            assert!(names.contains(&name), "missing command: {}", name);
+        }
+        for name in vec!["undo_tree::restore_selected_state",
+                         "replace::replace_current_match",
+                         "save_as::accept_path",
+                         "buffer_list::open_selected_buffer",
+                         "project_replace::apply",
+                         "command_mode::run"] {
+            assert!(!names.contains(&name), "mode-specific command listed: {}", name);
+        }
+
+        // None of the listed commands are internal, or bound only in other modes.
+        for name in names {
+            let modes = app.key_map.modes_binding(name);
+            assert!(!commands::INTERNAL_COMMANDS.contains(&name),
+                    "internal command listed: {}",
+                    name);
+            assert!(modes.is_empty() || modes.contains(&"normal") ||
+                    name.starts_with("application::switch_to_"),
+                    "{} is only bound in {:?}",
+                    name,
+                    modes);
+        }
+    }
+}
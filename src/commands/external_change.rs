@@ -0,0 +1,155 @@
+extern crate scribe;
+
+use commands;
+use helpers;
+use helpers::diff;
+use models::application::{Application, Message, Mode};
+use models::application::modes::external_change;
+
+/// Asks what to do about the current buffer if its file has changed on disk.
+pub fn check(app: &mut Application) {
+    let path = match app.workspace.current_buffer().and_then(|buffer| buffer.path.clone()) {
+        Some(path) => path,
+        None => return,
+    };
+
+    if app.disk_states.has_changed(&path) {
+        app.messages.push(Message::warning(format!("{} has changed on disk",
+                                                   path.to_string_lossy())));
+        app.mode = Mode::ExternalChange(external_change::new(path));
+    }
+}
+
+/// Replaces the buffer's content with that of its file, as a single operation.
+pub fn reload(app: &mut Application) {
+    let path = match app.mode {
+        Mode::ExternalChange(ref mode) => mode.path.clone(),
+        _ => return,
+    };
+
+    // Make sure the content being replaced can be returned to.
+    commands::undo_tree::start(app);
+    let result = helpers::read_file(&path).map(|data| {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            helpers::replace_content(buffer, &data);
+        }
+    });
+    match result {
+        Ok(_) => {
+            commands::buffer::mark_changed(app);
+            commands::buffer::detect_indentation(app);
+            app.disk_states.record(&path);
+            app.messages.push(Message::info(format!("Reloaded {}", path.to_string_lossy())));
+        }
+        Err(error) => {
+            app.messages.push(Message::error(format!("Couldn't reload {}: {}",
+                                                     path.to_string_lossy(),
+                                                     error)));
+        }
+    }
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Keeps the buffer's content, accepting that saving it will overwrite the file.
+pub fn keep_buffer(app: &mut Application) {
+    let path = match app.mode {
+        Mode::ExternalChange(ref mode) => mode.path.clone(),
+        _ => return,
+    };
+
+    app.disk_states.record(&path);
+    app.messages.push(Message::info(format!("Kept the buffer; saving will overwrite {}",
+                                            path.to_string_lossy())));
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Shows or hides the changes that reloading the buffer would make.
+pub fn toggle_diff(app: &mut Application) {
+    let buffer_data = match app.workspace.current_buffer() {
+        Some(buffer) => buffer.data(),
+        None => return,
+    };
+
+    if let Mode::ExternalChange(ref mut mode) = app.mode {
+        if mode.diff.is_some() {
+            mode.diff = None;
+            return;
+        }
+
+        match helpers::read_file(&mode.path) {
+            Ok(disk_data) => {
+                mode.diff = Some(diff::diff(&buffer_data, &disk_data));
+                mode.line_offset = 0;
+            }
+            Err(error) => {
+                app.messages.push(Message::error(format!("Couldn't read {}: {}",
+                                                         mode.path.to_string_lossy(),
+                                                         error)));
+            }
+        }
+    }
+}
+
+pub fn scroll_diff_down(app: &mut Application) {
+    if let Mode::ExternalChange(ref mut mode) = app.mode {
+        let line_count = mode.diff.as_ref().map(|diff| diff.len()).unwrap_or(0);
+        if mode.line_offset + 1 < line_count {
+            mode.line_offset += 1;
+        }
+    }
+}
+
+pub fn scroll_diff_up(app: &mut Application) {
+    if let Mode::ExternalChange(ref mut mode) = app.mode {
+        if mode.line_offset > 0 {
+            mode.line_offset -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use models::application::Mode;
+    use scribe::Buffer;
+
+    #[test]
+    fn check_and_reload_update_the_buffer_with_changes_on_disk() {
+        let path = env::temp_dir().join("amp_external_change_test.txt");
+        File::create(&path).unwrap().write_all(b"amp\n").unwrap();
+
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n");
+        buffer.path = Some(path.clone());
+        app.workspace.add_buffer(buffer);
+
+        // The first check records the file's state.
+        super::check(&mut app);
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+
+        File::create(&path).unwrap().write_all(b"amp\neditor\n").unwrap();
+        super::check(&mut app);
+        assert!(match app.mode {
+            Mode::ExternalChange(_) => true,
+            _ => false,
+        });
+
+        super::reload(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\neditor\n");
+
+        // Reloading is undone in a single step.
+        commands::buffer::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -2,23 +2,45 @@ use commands;
 use git2;
 use git2::{BranchType, Repository};
 use std::path::PathBuf;
-use models::application::{Application, ClipboardContent, Mode};
+use models::application::{Application, ClipboardContent, Message, Mode};
 use regex::Regex;
 
 pub fn add(app: &mut Application) {
-    if let Some(ref mut repo) = app.repository {
-        if let Some(buf) = app.workspace.current_buffer() {
-            if let Ok(ref mut index) = repo.index() {
-                if let Some(ref path) = buf.path {
-                    index.add_path(&path);
-                    index.write();
+    let message = match app.repository {
+        Some(ref mut repo) => {
+            match app.workspace.current_buffer() {
+                Some(buf) => {
+                    match buf.path {
+                        Some(ref path) => {
+                            let result = repo.index().and_then(|mut index| {
+                                try!(index.add_path(&path));
+                                index.write()
+                            });
+                            match result {
+                                Ok(_) => {
+                                    Message::info(format!("Added {} to the index",
+                                                          path.to_string_lossy()))
+                                }
+                                Err(error) => {
+                                    Message::error(format!("Couldn't add {} to the index: {}",
+                                                           path.to_string_lossy(),
+                                                           error))
+                                }
+                            }
+                        }
+                        None => Message::warning("Save the buffer before adding it to the index."),
+                    }
                 }
+                None => return,
             }
         }
-    }
+        None => Message::warning("There's no git repository in the workspace."),
+    };
+    app.messages.push(message);
 }
 
 pub fn copy_remote_url(app: &mut Application) {
+    let mut copied = false;
     if_let_chain! {
         [
             let Some(ref mut repo) = app.repository,
@@ -59,14 +81,24 @@ pub fn copy_remote_url(app: &mut Application) {
                             path.to_string_lossy(),
                             line_range
                         );
-                        app.clipboard.set_content(
+                        app.messages.push(Message::info(format!("Copied {}", gh_url)));
+                        copied = true;
+                        if let Err(error) = app.clipboard.set_content(
                             ClipboardContent::Inline(gh_url)
-                        );
+                        ) {
+                            app.messages.push(Message::error(format!(
+                                "Couldn't copy to the system clipboard: {}",
+                                error
+                            )));
+                        }
                     }
                 }
             }
         }
     }
+    if !copied {
+        app.messages.push(Message::warning("Couldn't build a GitHub URL for the buffer."));
+    }
     commands::application::switch_to_normal_mode(app);
 }
 
@@ -0,0 +1,117 @@
+use std::mem;
+use input;
+use models::application::{Application, Message, Mode};
+use models::application::modes::macros::{self, MacroAction};
+use rustbox::keyboard::Key;
+
+/// Stops recording the current macro, or waits for the
+/// name of a slot to start recording one into.
+pub fn toggle_recording(app: &mut Application) {
+    match app.macros.stop_recording() {
+        Some(name) => app.messages.push(Message::info(format!("Recorded macro @{}", name))),
+        None => app.mode = Mode::Macro(macros::new(MacroAction::Record)),
+    }
+}
+
+/// Records into, or plays, the macro slot typed in macro mode.
+pub fn select(app: &mut Application) {
+    let mode = match mem::replace(&mut app.mode, Mode::Normal) {
+        Mode::Macro(mode) => mode,
+        other_mode => {
+            app.mode = other_mode;
+            return;
+        }
+    };
+
+    if let Some(name) = mode.name {
+        match mode.action {
+            MacroAction::Record => app.macros.start_recording(name),
+            MacroAction::Play => play(app, name, mode.count),
+        }
+    }
+}
+
+/// Replays the keys that made up the most recent change to a buffer.
+pub fn repeat_last_change(app: &mut Application) {
+    // Repeating the last change doesn't replace it.
+    app.macros.discard_change();
+
+    let keys = app.macros.last_change();
+    replay(app, None, &keys, 1);
+}
+
+fn play(app: &mut Application, name: char, count: usize) {
+    match app.macros.get(name) {
+        Some(keys) => replay(app, Some(name), &keys, count),
+        None => {
+            app.messages.push(Message::warning(format!("There's no macro recorded in @{}.",
+                                                       name)))
+        }
+    }
+}
+
+/// Passes the keys to the current mode as if they were typed, the specified
+/// number of times, unless they're already being replayed (e.g. a macro that
+/// plays itself), as that would never end.
+fn replay(app: &mut Application, name: Option<char>, keys: &[Key], count: usize) {
+    if !app.macros.start_replay(name) {
+        return;
+    }
+
+    for _ in 0..count {
+        for &key in keys {
+            input::handle(app, key);
+        }
+    }
+    app.macros.end_replay();
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use input;
+    use models::application::Application;
+    use rustbox::keyboard::Key;
+    use scribe::Buffer;
+
+    fn type_keys(app: &mut Application, keys: &str) {
+        for c in keys.chars() {
+            input::handle(app, Key::Char(c));
+        }
+    }
+
+    #[test]
+    fn macros_are_recorded_and_played_the_specified_number_of_times() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nis\nhere\nnow");
+        app.workspace.add_buffer(buffer);
+
+        type_keys(&mut app, "Za;Z");
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "editor\nis\nhere\nnow");
+        assert_eq!(app.macros.get('a'), Some(vec![Key::Char(';')]));
+
+        type_keys(&mut app, "2@a");
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "here\nnow");
+    }
+
+    #[test]
+    fn repeat_last_change_ignores_commands_that_dont_change_the_buffer() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        type_keys(&mut app, "xj.");
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "mp\nditor");
+
+        // Undoing isn't treated as a change.
+        type_keys(&mut app, "u");
+        commands::cursor::move_up(&mut app);
+        type_keys(&mut app, ".");
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "p\neditor");
+    }
+}
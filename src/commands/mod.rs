@@ -1,16 +1,374 @@
-use models::application::Application;
+use std::collections::HashMap;
+use models::application::{Application, Mode};
 
 pub mod application;
 pub mod buffer;
+pub mod buffer_list;
+pub mod command_mode;
 pub mod cursor;
+pub mod external_change;
 pub mod git;
 pub mod jump_mode;
 pub mod line_jump;
+pub mod macros;
+pub mod multi_cursor;
 pub mod symbol_jump;
 pub mod open_mode;
+pub mod project_replace;
+pub mod project_search;
+pub mod register;
+pub mod replace;
+pub mod save_as;
 pub mod search;
 pub mod selection;
+pub mod session;
+pub mod swap;
+pub mod undo_tree;
+pub mod unsaved_changes;
 pub mod view;
 pub mod workspace;
 
 pub type Command = fn(&mut Application);
+
+/// Runs a command the specified number of times. Buffer changes made
+/// by the repetitions are grouped, so that they can be undone in one step.
+pub fn repeat(app: &mut Application, command: Command, count: usize) {
+    if count < 2 {
+        return command(app);
+    }
+
+    buffer::start_command_group(app);
+    app.repeating = true;
+    for _ in 0..count {
+        command(app);
+    }
+    app.repeating = false;
+
+    // Commands that switch to insert mode (e.g. "3o") leave their group
+    // open until the user is done typing; let insert mode close it.
+    match app.mode {
+        Mode::Insert(_) => (),
+        _ => buffer::end_command_group(app),
+    }
+}
+
+/// Commands that are only meant to be run by other commands, or by modes as
+/// input arrives, and not bound to keys; they're left out of the command palette.
+pub const INTERNAL_COMMANDS: [&'static str; 16] = ["buffer::detect_indentation",
+                                                   "buffer::end_command_group",
+                                                   "buffer::insert_char",
+                                                   "buffer::mark_changed",
+                                                   "buffer::start_command_group",
+                                                   "buffer_list::search",
+                                                   "command_mode::search",
+                                                   "jump_mode::match_tag",
+                                                   "macros::select",
+                                                   "open_mode::search",
+                                                   "register::select",
+                                                   "search::preview_result",
+                                                   "selection::delete",
+                                                   "symbol_jump::search",
+                                                   "undo_tree::record",
+                                                   "undo_tree::start"];
+
+/// Builds a registry of every command, keyed by its stable,
+/// module-qualified name (e.g. "buffer::save"), so that commands
+/// can be referenced by name from key maps, scripts, and tests.
+pub fn hash_map() -> HashMap<&'static str, Command> {
+    let mut commands: HashMap<&'static str, Command> = HashMap::new();
+
+    commands.insert("application::exit", application::exit);
+    commands.insert("application::switch_to_buffer_list_mode",
+                    application::switch_to_buffer_list_mode);
+    commands.insert("application::switch_to_command_mode",
+                    application::switch_to_command_mode);
+    commands.insert("application::switch_to_insert_mode", application::switch_to_insert_mode);
+    commands.insert("application::switch_to_jump_mode", application::switch_to_jump_mode);
+    commands.insert("application::switch_to_line_jump_mode", application::switch_to_line_jump_mode);
+    commands.insert("application::switch_to_macro_mode", application::switch_to_macro_mode);
+    commands.insert("application::switch_to_normal_mode", application::switch_to_normal_mode);
+    commands.insert("application::switch_to_open_mode", application::switch_to_open_mode);
+    commands.insert("application::switch_to_project_replace_insert_mode",
+                    application::switch_to_project_replace_insert_mode);
+    commands.insert("application::switch_to_project_search_mode",
+                    application::switch_to_project_search_mode);
+    commands.insert("application::switch_to_register_mode",
+                    application::switch_to_register_mode);
+    commands.insert("application::switch_to_replace_insert_mode",
+                    application::switch_to_replace_insert_mode);
+    commands.insert("application::switch_to_save_as_mode", application::switch_to_save_as_mode);
+    commands.insert("application::switch_to_search_insert_mode",
+                    application::switch_to_search_insert_mode);
+    commands.insert("application::switch_to_select_line_mode",
+                    application::switch_to_select_line_mode);
+    commands.insert("application::switch_to_select_mode", application::switch_to_select_mode);
+    commands.insert("application::switch_to_symbol_jump_mode",
+                    application::switch_to_symbol_jump_mode);
+    commands.insert("application::switch_to_undo_tree_mode",
+                    application::switch_to_undo_tree_mode);
+    commands.insert("buffer::backspace", buffer::backspace);
+    commands.insert("buffer::change_rest_of_line", buffer::change_rest_of_line);
+    commands.insert("buffer::change_token", buffer::change_token);
+    commands.insert("buffer::close", buffer::close);
+    commands.insert("buffer::close_without_saving", buffer::close_without_saving);
+    commands.insert("buffer::copy_current_line", buffer::copy_current_line);
+    commands.insert("buffer::cycle_paste", buffer::cycle_paste);
+    commands.insert("buffer::create_parent_directories_and_save",
+                    buffer::create_parent_directories_and_save);
+    commands.insert("buffer::delete", buffer::delete);
+    commands.insert("buffer::delete_current_line", buffer::delete_current_line);
+    commands.insert("buffer::delete_rest_of_line", buffer::delete_rest_of_line);
+    commands.insert("buffer::delete_token", buffer::delete_token);
+    commands.insert("buffer::detect_indentation", buffer::detect_indentation);
+    commands.insert("buffer::end_command_group", buffer::end_command_group);
+    commands.insert("buffer::ensure_trailing_newline", buffer::ensure_trailing_newline);
+    commands.insert("buffer::indent_line", buffer::indent_line);
+    commands.insert("buffer::insert_char", buffer::insert_char);
+    commands.insert("buffer::insert_newline", buffer::insert_newline);
+    commands.insert("buffer::mark_changed", buffer::mark_changed);
+    commands.insert("buffer::merge_next_line", buffer::merge_next_line);
+    commands.insert("buffer::outdent_line", buffer::outdent_line);
+    commands.insert("buffer::paste", buffer::paste);
+    commands.insert("buffer::paste_above", buffer::paste_above);
+    commands.insert("buffer::redo", buffer::redo);
+    commands.insert("buffer::remove_trailing_whitespace", buffer::remove_trailing_whitespace);
+    commands.insert("buffer::run_save_hooks", buffer::run_save_hooks);
+    commands.insert("buffer::save", buffer::save);
+    commands.insert("buffer::start_command_group", buffer::start_command_group);
+    commands.insert("buffer::undo", buffer::undo);
+    commands.insert("buffer_list::close_selected_buffer", buffer_list::close_selected_buffer);
+    commands.insert("buffer_list::open_previous_buffer", buffer_list::open_previous_buffer);
+    commands.insert("buffer_list::open_selected_buffer", buffer_list::open_selected_buffer);
+    commands.insert("buffer_list::search", buffer_list::search);
+    commands.insert("buffer_list::select_next_buffer", buffer_list::select_next_buffer);
+    commands.insert("buffer_list::select_previous_buffer", buffer_list::select_previous_buffer);
+    commands.insert("command_mode::run", command_mode::run);
+    commands.insert("command_mode::search", command_mode::search);
+    commands.insert("command_mode::select_next_command", command_mode::select_next_command);
+    commands.insert("command_mode::select_previous_command",
+                    command_mode::select_previous_command);
+    commands.insert("cursor::append_to_current_token", cursor::append_to_current_token);
+    commands.insert("cursor::insert_at_end_of_line", cursor::insert_at_end_of_line);
+    commands.insert("cursor::insert_at_first_word_of_line", cursor::insert_at_first_word_of_line);
+    commands.insert("cursor::insert_with_newline", cursor::insert_with_newline);
+    commands.insert("cursor::insert_with_newline_above", cursor::insert_with_newline_above);
+    commands.insert("cursor::move_down", cursor::move_down);
+    commands.insert("cursor::move_left", cursor::move_left);
+    commands.insert("cursor::move_right", cursor::move_right);
+    commands.insert("cursor::move_to_end_of_current_token", cursor::move_to_end_of_current_token);
+    commands.insert("cursor::move_to_end_of_line", cursor::move_to_end_of_line);
+    commands.insert("cursor::move_to_first_line", cursor::move_to_first_line);
+    commands.insert("cursor::move_to_first_word_of_line", cursor::move_to_first_word_of_line);
+    commands.insert("cursor::move_to_last_line", cursor::move_to_last_line);
+    commands.insert("cursor::move_to_start_of_line", cursor::move_to_start_of_line);
+    commands.insert("cursor::move_to_start_of_next_token", cursor::move_to_start_of_next_token);
+    commands.insert("cursor::move_to_start_of_previous_token",
+                    cursor::move_to_start_of_previous_token);
+    commands.insert("cursor::move_up", cursor::move_up);
+    commands.insert("external_change::check", external_change::check);
+    commands.insert("external_change::keep_buffer", external_change::keep_buffer);
+    commands.insert("external_change::reload", external_change::reload);
+    commands.insert("external_change::scroll_diff_down", external_change::scroll_diff_down);
+    commands.insert("external_change::scroll_diff_up", external_change::scroll_diff_up);
+    commands.insert("external_change::toggle_diff", external_change::toggle_diff);
+    commands.insert("git::add", git::add);
+    commands.insert("git::copy_remote_url", git::copy_remote_url);
+    commands.insert("jump_mode::match_tag", jump_mode::match_tag);
+    commands.insert("line_jump::accept_input", line_jump::accept_input);
+    commands.insert("macros::repeat_last_change", macros::repeat_last_change);
+    commands.insert("macros::select", macros::select);
+    commands.insert("macros::toggle_recording", macros::toggle_recording);
+    commands.insert("multi_cursor::add_at_next_occurrence",
+                    multi_cursor::add_at_next_occurrence);
+    commands.insert("multi_cursor::add_to_selected_lines", multi_cursor::add_to_selected_lines);
+    commands.insert("multi_cursor::clear", multi_cursor::clear);
+    commands.insert("open_mode::open", open_mode::open);
+    commands.insert("open_mode::search", open_mode::search);
+    commands.insert("open_mode::select_next_path", open_mode::select_next_path);
+    commands.insert("open_mode::select_previous_path", open_mode::select_previous_path);
+    commands.insert("project_replace::accept_input", project_replace::accept_input);
+    commands.insert("project_replace::apply", project_replace::apply);
+    commands.insert("project_replace::select_next_change", project_replace::select_next_change);
+    commands.insert("project_replace::select_previous_change",
+                    project_replace::select_previous_change);
+    commands.insert("project_replace::toggle_selected_change",
+                    project_replace::toggle_selected_change);
+    commands.insert("project_search::accept", project_search::accept);
+    commands.insert("project_search::select_next_result", project_search::select_next_result);
+    commands.insert("project_search::select_previous_result",
+                    project_search::select_previous_result);
+    commands.insert("register::cancel", register::cancel);
+    commands.insert("register::select", register::select);
+    commands.insert("replace::accept_input", replace::accept_input);
+    commands.insert("replace::replace_all", replace::replace_all);
+    commands.insert("replace::replace_current_match", replace::replace_current_match);
+    commands.insert("replace::skip_current_match", replace::skip_current_match);
+    commands.insert("save_as::accept_path", save_as::accept_path);
+    commands.insert("save_as::complete_path", save_as::complete_path);
+    commands.insert("search::accept_query", search::accept_query);
+    commands.insert("search::cancel", search::cancel);
+    commands.insert("search::move_to_next_result", search::move_to_next_result);
+    commands.insert("search::move_to_previous_result", search::move_to_previous_result);
+    commands.insert("search::preview_result", search::preview_result);
+    commands.insert("search::toggle_regex", search::toggle_regex);
+    commands.insert("selection::change", selection::change);
+    commands.insert("selection::copy", selection::copy);
+    commands.insert("selection::copy_and_delete", selection::copy_and_delete);
+    commands.insert("selection::delete", selection::delete);
+    commands.insert("session::restore", session::restore);
+    commands.insert("session::save", session::save);
+    commands.insert("swap::check", swap::check);
+    commands.insert("swap::discard", swap::discard);
+    commands.insert("swap::recover", swap::recover);
+    commands.insert("swap::remove", swap::remove);
+    commands.insert("swap::write", swap::write);
+    commands.insert("symbol_jump::jump_to_selected_symbol", symbol_jump::jump_to_selected_symbol);
+    commands.insert("symbol_jump::search", symbol_jump::search);
+    commands.insert("symbol_jump::select_next_symbol", symbol_jump::select_next_symbol);
+    commands.insert("symbol_jump::select_previous_symbol", symbol_jump::select_previous_symbol);
+    commands.insert("undo_tree::next_branch", undo_tree::next_branch);
+    commands.insert("undo_tree::previous_branch", undo_tree::previous_branch);
+    commands.insert("undo_tree::record", undo_tree::record);
+    commands.insert("undo_tree::redo", undo_tree::redo);
+    commands.insert("undo_tree::restore_selected_state", undo_tree::restore_selected_state);
+    commands.insert("undo_tree::save", undo_tree::save);
+    commands.insert("undo_tree::select_next_state", undo_tree::select_next_state);
+    commands.insert("undo_tree::select_previous_state", undo_tree::select_previous_state);
+    commands.insert("undo_tree::start", undo_tree::start);
+    commands.insert("undo_tree::undo", undo_tree::undo);
+    commands.insert("unsaved_changes::discard", unsaved_changes::discard);
+    commands.insert("unsaved_changes::save", unsaved_changes::save);
+    commands.insert("view::scroll_cursor_to_center", view::scroll_cursor_to_center);
+    commands.insert("view::scroll_down", view::scroll_down);
+    commands.insert("view::scroll_to_cursor", view::scroll_to_cursor);
+    commands.insert("view::scroll_up", view::scroll_up);
+    commands.insert("view::toggle_theme", view::toggle_theme);
+    commands.insert("workspace::next_buffer", workspace::next_buffer);
+    commands.insert("workspace::switch_to_previous_buffer",
+                    workspace::switch_to_previous_buffer);
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    #[test]
+    fn repeat_runs_the_command_the_specified_number_of_times() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nis\nhere");
+        app.workspace.add_buffer(buffer);
+
+        super::repeat(&mut app, super::cursor::move_down, 3);
+
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 3);
+    }
+
+    #[test]
+    fn repeat_groups_changes_as_a_single_operation() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor\nis\nhere");
+        app.workspace.add_buffer(buffer);
+
+        super::repeat(&mut app, super::buffer::delete_current_line, 3);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "here");
+
+        super::buffer::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "amp\neditor\nis\nhere");
+    }
+
+    #[test]
+    fn hash_map_includes_commands_from_every_module() {
+        let commands = super::hash_map();
+
+        for name in vec!["application::exit",
+                         "buffer::save",
+                         "cursor::move_down",
+                         "git::copy_remote_url",
+                         "jump_mode::match_tag",
+                         "line_jump::accept_input",
+                         "open_mode::open",
+                         "search::accept_query",
+                         "selection::copy",
+                         "symbol_jump::search",
+                         "undo_tree::undo",
+                         "view::toggle_theme",
+                         "workspace::next_buffer"] {
+            assert!(commands.contains_key(name), "missing command: {}", name);
+        }
+    }
+
+    #[test]
+    fn hash_map_includes_every_public_command() {
+        let commands = super::hash_map();
+        let modules = vec![("application", include_str!("application.rs")),
+                           ("buffer", include_str!("buffer.rs")),
+                           ("buffer_list", include_str!("buffer_list.rs")),
+                           ("command_mode", include_str!("command_mode.rs")),
+                           ("cursor", include_str!("cursor.rs")),
+                           ("external_change", include_str!("external_change.rs")),
+                           ("git", include_str!("git.rs")),
+                           ("jump_mode", include_str!("jump_mode.rs")),
+                           ("line_jump", include_str!("line_jump.rs")),
+                           ("macros", include_str!("macros.rs")),
+                           ("multi_cursor", include_str!("multi_cursor.rs")),
+                           ("symbol_jump", include_str!("symbol_jump.rs")),
+                           ("open_mode", include_str!("open_mode.rs")),
+                           ("project_replace", include_str!("project_replace.rs")),
+                           ("project_search", include_str!("project_search.rs")),
+                           ("register", include_str!("register.rs")),
+                           ("replace", include_str!("replace.rs")),
+                           ("save_as", include_str!("save_as.rs")),
+                           ("search", include_str!("search.rs")),
+                           ("selection", include_str!("selection.rs")),
+                           ("session", include_str!("session.rs")),
+                           ("swap", include_str!("swap.rs")),
+                           ("undo_tree", include_str!("undo_tree.rs")),
+                           ("unsaved_changes", include_str!("unsaved_changes.rs")),
+                           ("view", include_str!("view.rs")),
+                           ("workspace", include_str!("workspace.rs"))];
+
+        // Make sure that new modules are checked, too.
+        let module_count = include_str!("mod.rs")
+                               .lines()
+                               .filter(|line| line.starts_with("pub mod "))
+                               .count();
+        assert_eq!(modules.len(), module_count);
+
+        for (module, source) in modules {
+            // Commands are public functions that only take the application,
+            // outside of the module's tests.
+            let source = source.split("#[cfg(test)]").next().unwrap();
+            for line in source.lines() {
+                if line.starts_with("pub fn ") && line.ends_with("(app: &mut Application) {") {
+                    let name = format!("{}::{}", module, &line[7..line.find('(').unwrap()]);
+                    assert!(commands.contains_key(&name[..]), "unregistered command: {}", name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hash_map_commands_can_be_invoked_by_name() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        let command = super::hash_map().get("cursor::move_down").map(|c| *c).unwrap();
+        command(&mut app);
+
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 1,
+                       offset: 0,
+                   });
+    }
+}
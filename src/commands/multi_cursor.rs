@@ -0,0 +1,367 @@
+extern crate scribe;
+
+use std::cmp;
+use commands;
+use models::application::{Application, Edit, Message, Mode};
+use scribe::buffer::Position;
+
+/// Adds a cursor at the next occurrence of the selection (in select mode) or
+/// the word at the cursor, after the most recently added one, wrapping around
+/// to the start of the buffer. Cursors are placed at the same offset within
+/// the occurrence as the buffer's own cursor is within the original.
+pub fn add_at_next_occurrence(app: &mut Application) {
+    let revision = commands::buffer::revision(app);
+    let latest_cursor = app.cursors.positions(revision).last().map(|position| *position);
+    let mut existing_cursors = app.cursors.positions(revision).to_vec();
+
+    let position = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            let data = buffer.data();
+            let characters: Vec<char> = data.chars().collect();
+            let cursor = index_of(&data, *buffer.cursor);
+            existing_cursors.push(*buffer.cursor);
+
+            // Only match whole words when looking for the word at the cursor.
+            let (start, end, whole_words) = match app.mode {
+                Mode::Select(ref mode) => {
+                    let anchor = index_of(&data, mode.anchor);
+                    (cmp::min(anchor, cursor), cmp::max(anchor, cursor), false)
+                }
+                _ => {
+                    let (start, end) = word_bounds(&characters, cursor);
+                    (start, end, true)
+                }
+            };
+            if start == end {
+                app.messages.push(Message::warning("There's nothing at the cursor to look for."));
+                return;
+            }
+
+            let pattern = &characters[start..end];
+            let cursor_offset = cursor - start;
+            let search_start = match latest_cursor {
+                Some(position) => index_of(&data, position).saturating_sub(cursor_offset),
+                None => start,
+            };
+
+            // Look from just after the latest occurrence to the end
+            // of the buffer, and then from the start back up to it.
+            let candidates = (search_start + 1..characters.len()).chain(0..search_start + 1);
+            let mut found = None;
+            for index in candidates {
+                if !occurs_at(&characters, pattern, index, whole_words) {
+                    continue;
+                }
+
+                let position = position_at(&data, index + cursor_offset);
+                if !existing_cursors.contains(&position) {
+                    found = Some(position);
+                    break;
+                }
+            }
+
+            found
+        }
+        None => return,
+    };
+
+    match position {
+        Some(position) => {
+            app.cursors.add(revision, position);
+        }
+        None => app.messages.push(Message::warning("There are no other occurrences to add.")),
+    }
+}
+
+/// Adds a cursor to each of the selected lines (in select line mode) other than
+/// the current one, at the cursor's offset (or the end of shorter lines).
+pub fn add_to_selected_lines(app: &mut Application) {
+    let revision = commands::buffer::revision(app);
+    let positions = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            match app.mode {
+                Mode::SelectLine(ref mode) => {
+                    let data = buffer.data();
+                    let first_line = cmp::min(mode.anchor, buffer.cursor.line);
+                    let last_line = cmp::max(mode.anchor, buffer.cursor.line);
+
+                    data.split('\n')
+                        .enumerate()
+                        .skip(first_line)
+                        .take(last_line - first_line + 1)
+                        .filter(|&(line, _)| line != buffer.cursor.line)
+                        .map(|(line, content)| {
+                            Position {
+                                line: line,
+                                offset: cmp::min(buffer.cursor.offset, content.chars().count()),
+                            }
+                        })
+                        .collect()
+                }
+                _ => Vec::new(),
+            }
+        }
+        None => Vec::new(),
+    };
+
+    for position in positions {
+        app.cursors.add(revision, position);
+    }
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Removes the current buffer's additional cursors.
+pub fn clear(app: &mut Application) {
+    app.cursors.clear();
+}
+
+/// Makes an edit at the current buffer's cursor, and again at each of its
+/// additional cursors, as a single operation. Edits are made from the end of
+/// the buffer to the start, so that each one leaves the positions of those yet
+/// to be made intact, shifting the cursors already edited at by its extent.
+pub fn at_each<F>(app: &mut Application, mut edit: F)
+    where F: FnMut(&mut Application) -> Option<Edit>
+{
+    let revision = commands::buffer::revision(app);
+    let additional_cursors = app.cursors.positions(revision).to_vec();
+    if additional_cursors.is_empty() {
+        edit(app);
+        return;
+    }
+
+    // Track each cursor's position, flagging the buffer's own.
+    let mut cursors = match app.workspace.current_buffer() {
+        Some(buffer) => vec![(*buffer.cursor, true)],
+        None => return,
+    };
+    for position in additional_cursors {
+        cursors.push((position, false));
+    }
+    cursors.sort_by(|a, b| (b.0.line, b.0.offset).cmp(&(a.0.line, a.0.offset)));
+
+    commands::buffer::start_command_group(app);
+    let mut edited_cursors: Vec<(Position, bool)> = Vec::new();
+    for (position, own_cursor) in cursors {
+        match app.workspace.current_buffer() {
+            Some(buffer) => buffer.cursor.move_to(position),
+            None => return,
+        };
+
+        if let Some(change) = edit(app) {
+            for edited_cursor in edited_cursors.iter_mut() {
+                edited_cursor.0 = change.shift(edited_cursor.0);
+            }
+        }
+
+        if let Some(buffer) = app.workspace.current_buffer() {
+            edited_cursors.push((*buffer.cursor, own_cursor));
+        }
+    }
+
+    // Commands that leave us in insert mode
+    // leave their group open until we're done typing.
+    match app.mode {
+        Mode::Insert(_) => (),
+        _ => commands::buffer::end_command_group(app),
+    }
+
+    // Put the buffer's cursor back, and drop cursors that edits have merged.
+    let mut positions = Vec::new();
+    if let Some(buffer) = app.workspace.current_buffer() {
+        for &(position, own_cursor) in edited_cursors.iter().rev() {
+            if own_cursor {
+                buffer.cursor.move_to(position);
+            } else if !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+        let own_position = *buffer.cursor;
+        positions.retain(|position| *position != own_position);
+    }
+
+    // The edits have changed the buffer; keep the cursors for its new revision.
+    let revision = commands::buffer::revision(app);
+    app.cursors.set(revision, positions);
+    commands::view::scroll_to_cursor(app);
+}
+
+/// The bounds of the word (letters, numbers, and underscores) at, or ending
+/// just before, the specified character, which are equal if there isn't one.
+fn word_bounds(characters: &[char], index: usize) -> (usize, usize) {
+    let mut start = cmp::min(index, characters.len());
+    while start > 0 && is_word_character(characters[start - 1]) {
+        start -= 1;
+    }
+
+    let mut end = start;
+    while end < characters.len() && is_word_character(characters[end]) {
+        end += 1;
+    }
+
+    (start, end)
+}
+
+fn is_word_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// Whether the pattern occurs at the specified index, which must
+/// not be part of a larger word, if only whole words are to match.
+fn occurs_at(characters: &[char], pattern: &[char], index: usize, whole_words: bool) -> bool {
+    let end = index + pattern.len();
+    if end > characters.len() || &characters[index..end] != pattern {
+        return false;
+    }
+
+    !whole_words ||
+    ((index == 0 || !is_word_character(characters[index - 1])) &&
+     (end == characters.len() || !is_word_character(characters[end])))
+}
+
+/// Converts a position to the index of the character at it,
+/// limiting offsets to the end of their lines.
+fn index_of(data: &str, position: Position) -> usize {
+    let mut index = 0;
+    for (line, content) in data.split('\n').enumerate() {
+        let length = content.chars().count();
+        if line == position.line {
+            return index + cmp::min(position.offset, length);
+        }
+
+        // Include the newline character.
+        index += length + 1;
+    }
+
+    data.chars().count()
+}
+
+/// Converts a character index to its position, limiting it to the end of the data.
+fn position_at(data: &str, index: usize) -> Position {
+    let mut position = Position {
+        line: 0,
+        offset: 0,
+    };
+
+    for character in data.chars().take(index) {
+        if character == '\n' {
+            position.line += 1;
+            position.offset = 0;
+        } else {
+            position.offset += 1;
+        }
+    }
+
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use models::application::{ClipboardContent, Mode};
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    #[test]
+    fn add_at_next_occurrence_skips_partial_words_and_wraps_around() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp ample amp\namp");
+        app.workspace.add_buffer(buffer);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position {
+            line: 0,
+            offset: 11,
+        });
+
+        super::add_at_next_occurrence(&mut app);
+        super::add_at_next_occurrence(&mut app);
+        super::add_at_next_occurrence(&mut app);
+
+        let revision = commands::buffer::revision(&mut app);
+        assert_eq!(app.cursors.positions(revision).to_vec(),
+                   vec![Position {
+                            line: 1,
+                            offset: 1,
+                        },
+                        Position {
+                            line: 0,
+                            offset: 1,
+                        }]);
+        assert_eq!(app.messages.len(), 1);
+    }
+
+    #[test]
+    fn add_to_selected_lines_adds_cursors_at_the_cursor_offset() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\na\neditor");
+        app.workspace.add_buffer(buffer);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position {
+            line: 0,
+            offset: 2,
+        });
+
+        commands::application::switch_to_select_line_mode(&mut app);
+        commands::cursor::move_down(&mut app);
+        commands::cursor::move_down(&mut app);
+        super::add_to_selected_lines(&mut app);
+
+        let revision = commands::buffer::revision(&mut app);
+        assert_eq!(app.cursors.positions(revision).to_vec(),
+                   vec![Position {
+                            line: 0,
+                            offset: 2,
+                        },
+                        Position {
+                            line: 1,
+                            offset: 1,
+                        }]);
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn edits_are_made_at_every_cursor_and_undone_together() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\namp\namp");
+        app.workspace.add_buffer(buffer);
+        let revision = commands::buffer::revision(&mut app);
+        app.cursors.add(revision,
+                        Position {
+                            line: 1,
+                            offset: 0,
+                        });
+        app.cursors.add(revision,
+                        Position {
+                            line: 2,
+                            offset: 0,
+                        });
+
+        commands::buffer::delete(&mut app);
+        commands::application::switch_to_insert_mode(&mut app);
+        for character in "an".chars() {
+            if let Mode::Insert(ref mut mode) = app.mode {
+                mode.input = Some(character);
+            }
+            commands::buffer::insert_char(&mut app);
+        }
+        commands::application::switch_to_normal_mode(&mut app);
+        commands::undo_tree::record(&mut app);
+        app.clipboard.set_content(ClipboardContent::Inline("!".to_string())).unwrap();
+        commands::buffer::paste(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "an!mp\nan!mp\nan!mp");
+
+        commands::buffer::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "anmp\nanmp\nanmp");
+
+        // Undoing moves content out from under the cursors, so they're dropped.
+        let revision = commands::buffer::revision(&mut app);
+        assert!(app.cursors.positions(revision).is_empty());
+    }
+}
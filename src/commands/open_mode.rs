@@ -5,16 +5,12 @@ use commands;
 use models::application::{Application, Mode};
 
 pub fn open(app: &mut Application) {
-    match app.mode {
-        Mode::Open(ref mut mode) => {
-            match mode.selected_path() {
-                Some(path) => {
-                    app.workspace.open_buffer(path.clone());
-                }
-                None => (),
-            }
-        }
-        _ => (),
+    let path = match app.mode {
+        Mode::Open(ref mode) => mode.selected_path().map(|path| path.clone()),
+        _ => None,
+    };
+    if let Some(path) = path {
+        commands::buffer::open_file(app, path);
     }
 
     commands::application::switch_to_normal_mode(app);
@@ -0,0 +1,233 @@
+extern crate scribe;
+
+use commands;
+use helpers;
+use std::collections::HashMap;
+use models::application::{Application, Message, Mode};
+use models::application::undo_tree;
+use models::application::modes::ProjectReplaceMode;
+use models::application::modes::project_replace::ProjectReplacement;
+use scribe::Buffer;
+
+/// Advances from the query prompt to the replacement prompt, or, once both
+/// have been entered, previews the changes needed throughout the workspace.
+pub fn accept_input(app: &mut Application) {
+    let (query, replacement, regex) = match app.mode {
+        Mode::ProjectReplaceInsert(ref mut mode) => {
+            if mode.replacement.is_none() {
+                // Don't move on without something valid to replace.
+                if mode.regex {
+                    if let Err(error) = helpers::search::compile(&mode.query) {
+                        mode.error = Some(error);
+                        return;
+                    }
+                }
+                if !mode.query.is_empty() {
+                    mode.replacement = Some(String::new());
+                }
+
+                return;
+            }
+
+            (mode.query.clone(), mode.replacement.clone().unwrap_or(String::new()), mode.regex)
+        }
+        _ => return,
+    };
+
+    // Open buffers may have unsaved changes, so preview those instead of what's on disk.
+    let workspace_path = app.workspace.path.clone();
+    let mut open_buffers = HashMap::new();
+    helpers::workspace::each_buffer(&mut app.workspace, |buffer| {
+        if let Some(ref path) = buffer.path {
+            open_buffers.insert(workspace_path.join(path), buffer.data());
+        }
+    });
+
+    app.mode = Mode::ProjectReplace(ProjectReplaceMode::new(workspace_path,
+                                                            &query,
+                                                            &replacement,
+                                                            regex,
+                                                            &open_buffers));
+}
+
+pub fn select_next_change(app: &mut Application) {
+    if let Mode::ProjectReplace(ref mut mode) = app.mode {
+        if !mode.changes.is_empty() {
+            mode.changes.select_next();
+        }
+    }
+}
+
+pub fn select_previous_change(app: &mut Application) {
+    if let Mode::ProjectReplace(ref mut mode) = app.mode {
+        mode.changes.select_previous();
+    }
+}
+
+pub fn toggle_selected_change(app: &mut Application) {
+    if let Mode::ProjectReplace(ref mut mode) = app.mode {
+        mode.toggle_selected_change();
+    }
+}
+
+/// Applies the previewed changes that haven't been excluded. Open buffers are
+/// updated in-place (and left unsaved), while other files are changed on disk.
+pub fn apply(app: &mut Application) {
+    let (mut grouped_changes, truncated) = match app.mode {
+        Mode::ProjectReplace(ref mode) => (mode.included_changes(), mode.truncated),
+        _ => return,
+    };
+
+    let workspace_path = app.workspace.path.clone();
+    let persistent = app.preferences.persistent_undo();
+    let mut changed_buffers = Vec::new();
+    {
+        let undo_trees = &mut app.undo_trees;
+        let revisions = &app.revisions;
+        let messages = &mut app.messages;
+        helpers::workspace::each_buffer(&mut app.workspace, |buffer| {
+            let buffer_path = match buffer.path {
+                Some(ref path) => workspace_path.join(path),
+                None => return,
+            };
+            let index = grouped_changes.iter()
+                                       .position(|&(ref path, _)| {
+                                           workspace_path.join(path) == buffer_path
+                                       });
+
+            if let Some(index) = index {
+                // Buffers that haven't been changed yet may not have an undo tree;
+                // start one before replacing their content, so it can be undone.
+                if let Some(id) = buffer.id {
+                    if let Err(error) = undo_tree::start(undo_trees,
+                                                         buffer,
+                                                         revisions.get(id),
+                                                         &workspace_path,
+                                                         persistent) {
+                        messages.push(Message::error(error));
+                    }
+                }
+
+                let (_, changes) = grouped_changes.remove(index);
+                apply_changes(buffer, &changes);
+                changed_buffers.extend(buffer.id);
+            }
+        });
+    }
+    for id in changed_buffers {
+        app.revisions.increment(id);
+    }
+
+    for (path, changes) in grouped_changes {
+        let error = match Buffer::from_file(workspace_path.join(&path)) {
+            Ok(mut buffer) => {
+                apply_changes(&mut buffer, &changes);
+                buffer.save()
+            }
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            app.messages.push(Message::error(format!("Couldn't replace in {}: {}",
+                                                     path.to_string_lossy(),
+                                                     error)));
+        }
+    }
+
+    commands::application::switch_to_normal_mode(app);
+    if truncated {
+        app.messages.push(Message::warning("There were too many matches to replace at once; \
+                                            files beyond those changed were left out. Replace \
+                                            again to continue."));
+    }
+}
+
+/// Applies a file's changes as a single operation, so that they can be undone in one step.
+fn apply_changes(buffer: &mut Buffer, changes: &Vec<ProjectReplacement>) {
+    let original_position = *buffer.cursor;
+    buffer.start_operation_group();
+
+    // Replace matches in reverse order, so that
+    // earlier ranges aren't shifted by the changes.
+    for change in changes.iter().rev() {
+        buffer.delete_range(change.range.clone());
+        buffer.cursor.move_to(change.range.start());
+        buffer.insert(&change.replacement);
+    }
+
+    buffer.end_operation_group();
+    buffer.cursor.move_to(original_position);
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use helpers::SelectableSet;
+    use models::application::{Application, Mode};
+    use models::application::modes::ProjectReplaceMode;
+    use models::application::modes::project_replace::ProjectReplacement;
+    use scribe::Buffer;
+    use scribe::buffer::{Position, Range};
+
+    fn change(line: usize, replacement: &str) -> ProjectReplacement {
+        ProjectReplacement {
+            path: PathBuf::from("project_replace_test.rs"),
+            range: Range::new(Position {
+                                  line: line,
+                                  offset: 0,
+                              },
+                              Position {
+                                  line: line,
+                                  offset: 4,
+                              }),
+            replacement: replacement.to_string(),
+            preview: String::new(),
+        }
+    }
+
+    fn set_up_application() -> Application {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("edit\nedit\nedit");
+        buffer.path = Some(PathBuf::from("project_replace_test.rs"));
+        app.workspace.add_buffer(buffer);
+
+        app
+    }
+
+    #[test]
+    fn apply_updates_open_buffers_in_place_skipping_exclusions() {
+        let mut app = set_up_application();
+        let mut excluded = HashSet::new();
+        excluded.insert(1);
+        app.mode = Mode::ProjectReplace(ProjectReplaceMode {
+            changes: SelectableSet::new(vec![change(0, "amp"), change(1, "amp"), change(2, "amp")]),
+            excluded: excluded,
+            truncated: false,
+        });
+
+        commands::project_replace::apply(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\nedit\namp");
+
+        // Ensure that a single undo reverts all replacements.
+        commands::buffer::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "edit\nedit\nedit");
+    }
+
+    #[test]
+    fn apply_warns_when_files_have_been_left_out() {
+        let mut app = set_up_application();
+        app.mode = Mode::ProjectReplace(ProjectReplaceMode {
+            changes: SelectableSet::new(vec![change(0, "amp")]),
+            excluded: HashSet::new(),
+            truncated: true,
+        });
+
+        commands::project_replace::apply(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\nedit\nedit");
+        assert_eq!(app.messages.len(), 1);
+    }
+}
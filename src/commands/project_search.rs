@@ -0,0 +1,46 @@
+use commands;
+use models::application::{Application, Mode};
+
+/// Searches the project for the input, or, if its results are already
+/// listed, opens the selected one and moves the cursor to the match.
+pub fn accept(app: &mut Application) {
+    let selected_result = match app.mode {
+        Mode::ProjectSearch(ref mut mode) => {
+            if mode.results.is_none() {
+                mode.search();
+                return;
+            }
+
+            mode.selected_result().map(|result| result.clone())
+        }
+        _ => return,
+    };
+
+    if let Some(result) = selected_result {
+        commands::buffer::open_file(app, result.path);
+        if let Some(buffer) = app.workspace.current_buffer() {
+            buffer.cursor.move_to(result.position);
+        }
+
+        commands::application::switch_to_normal_mode(app);
+        commands::view::scroll_cursor_to_center(app);
+    }
+}
+
+pub fn select_next_result(app: &mut Application) {
+    if let Mode::ProjectSearch(ref mut mode) = app.mode {
+        if let Some(ref mut results) = mode.results {
+            if !results.is_empty() {
+                results.select_next();
+            }
+        }
+    }
+}
+
+pub fn select_previous_result(app: &mut Application) {
+    if let Mode::ProjectSearch(ref mut mode) = app.mode {
+        if let Some(ref mut results) = mode.results {
+            results.select_previous();
+        }
+    }
+}
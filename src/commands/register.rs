@@ -0,0 +1,73 @@
+use std::mem;
+use models::application::modes::jump;
+use models::application::{Application, Mode};
+
+/// Uses the typed register for the next copy or paste,
+/// returning to the mode the register was selected from.
+pub fn select(app: &mut Application) {
+    if let Some(name) = leave_register_mode(app) {
+        app.clipboard.select_register(name);
+    }
+}
+
+/// Returns to the mode the register was being selected from, without selecting one.
+pub fn cancel(app: &mut Application) {
+    leave_register_mode(app);
+}
+
+/// Switches back to the previous select mode, if there was one,
+/// returning the typed register name, if there was one.
+fn leave_register_mode(app: &mut Application) -> Option<char> {
+    match mem::replace(&mut app.mode, Mode::Normal) {
+        Mode::Register(register_mode) => {
+            match register_mode.select_mode {
+                jump::SelectModeOptions::None => (),
+                jump::SelectModeOptions::Select(select_mode) => {
+                    app.mode = Mode::Select(select_mode);
+                }
+                jump::SelectModeOptions::SelectLine(select_mode) => {
+                    app.mode = Mode::SelectLine(select_mode);
+                }
+            }
+
+            register_mode.name
+        }
+        other_mode => {
+            app.mode = other_mode;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use models::application::{ClipboardContent, Mode};
+    use scribe::Buffer;
+
+    #[test]
+    fn select_returns_to_select_line_mode_and_uses_the_register_for_the_next_copy() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_select_line_mode(&mut app);
+        commands::application::switch_to_register_mode(&mut app);
+        if let Mode::Register(ref mut mode) = app.mode {
+            mode.name = Some('a');
+        }
+        super::select(&mut app);
+        assert!(match app.mode {
+            Mode::SelectLine(_) => true,
+            _ => false,
+        });
+
+        commands::selection::copy(&mut app);
+        app.clipboard.select_register('a');
+        assert_eq!(*app.clipboard.get_content(),
+                   ClipboardContent::Block("amp\n".to_string()));
+    }
+}
@@ -0,0 +1,318 @@
+extern crate scribe;
+
+use commands;
+use helpers::search;
+use models::application::{Application, Mode};
+use models::application::modes::replace_confirm;
+use scribe::Buffer;
+use scribe::buffer::{Position, Range};
+
+/// Advances from the query prompt to the replacement prompt, or, once
+/// both have been entered, starts stepping through matches to confirm them.
+pub fn accept_input(app: &mut Application) {
+    let (query, replacement, regex) = match app.mode {
+        Mode::ReplaceInsert(ref mut mode) => {
+            if mode.replacement.is_none() {
+                // Don't move on without something valid to replace.
+                if mode.regex {
+                    if let Err(error) = search::compile(&mode.query) {
+                        mode.error = Some(error);
+                        return;
+                    }
+                }
+                if !mode.query.is_empty() {
+                    mode.replacement = Some(String::new());
+                }
+
+                return;
+            }
+
+            (mode.query.clone(), mode.replacement.clone().unwrap_or(String::new()), mode.regex)
+        }
+        _ => return,
+    };
+
+    // Group all of the replacements, so that they can be undone in one step.
+    // The group is closed when we switch back to normal mode.
+    app.mode = Mode::ReplaceConfirm(replace_confirm::new(query.clone(), replacement, regex));
+    commands::buffer::start_command_group(app);
+    app.search_query = Some(query);
+    app.search_regex = regex;
+
+    let cursor_position = match app.workspace.current_buffer() {
+        Some(buffer) => *buffer.cursor,
+        None => return,
+    };
+    select_next_match(app, cursor_position);
+}
+
+/// Replaces the current match and moves on to the next one.
+pub fn replace_current_match(app: &mut Application) {
+    let mut next_position = None;
+
+    if let Mode::ReplaceConfirm(ref mut mode) = app.mode {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            if let Some((range, replacement)) = mode.current_match.take() {
+                replace(buffer, &range, &replacement);
+
+                // Resume searching after the replacement text.
+                next_position = Some(Position {
+                    line: range.start().line,
+                    offset: range.start().offset + replacement.chars().count(),
+                });
+            }
+        }
+    }
+
+    // We've only moved on if we've replaced the match.
+    if next_position.is_some() {
+        commands::buffer::mark_changed(app);
+    }
+    match next_position {
+        Some(position) => select_next_match(app, position),
+        None => commands::application::switch_to_normal_mode(app),
+    }
+}
+
+/// Leaves the current match as-is and moves on to the next one.
+pub fn skip_current_match(app: &mut Application) {
+    let next_position = match app.mode {
+        Mode::ReplaceConfirm(ref mut mode) => {
+            mode.current_match.take().map(|(range, _)| range.end())
+        }
+        _ => None,
+    };
+
+    match next_position {
+        Some(position) => select_next_match(app, position),
+        None => commands::application::switch_to_normal_mode(app),
+    }
+}
+
+/// Replaces every match in the buffer as a single operation. When
+/// confirming individual matches, replaces the current and all
+/// remaining matches, leaving previously skipped ones untouched.
+pub fn replace_all(app: &mut Application) {
+    let start_of_buffer = Position {
+        line: 0,
+        offset: 0,
+    };
+    let (query, replacement, regex, start_position) = match app.mode {
+        Mode::ReplaceInsert(ref mut mode) => {
+            let replacement = match mode.replacement {
+                Some(ref replacement) => replacement.clone(),
+                None => return,
+            };
+
+            // The regex flag can be toggled after the query's been
+            // accepted, so it may not have been validated yet.
+            if mode.regex {
+                if let Err(error) = search::compile(&mode.query) {
+                    mode.error = Some(error);
+                    return;
+                }
+            }
+
+            (mode.query.clone(), replacement, mode.regex, start_of_buffer)
+        }
+        Mode::ReplaceConfirm(ref mode) => {
+            let start_position = match mode.current_match {
+                Some((ref range, _)) => range.start(),
+                None => start_of_buffer,
+            };
+
+            (mode.query.clone(), mode.replacement.clone(), mode.regex, start_position)
+        }
+        _ => return,
+    };
+
+    commands::buffer::start_command_group(app);
+    if let Some(buffer) = app.workspace.current_buffer() {
+        let original_position = *buffer.cursor;
+        let data = buffer.data();
+        let matches: Vec<(Range, String)> =
+            search::find_all_replacements(&data, &query, &replacement, regex)
+                .into_iter()
+                .filter(|&(ref range, _)| range.start() >= start_position)
+                .collect();
+
+        // Replace matches in reverse order, so that
+        // earlier ranges aren't shifted by the changes.
+        for &(ref range, ref replacement) in matches.iter().rev() {
+            replace(buffer, range, replacement);
+        }
+
+        buffer.cursor.move_to(original_position);
+    }
+    commands::buffer::mark_changed(app);
+
+    app.search_query = Some(query);
+    app.search_regex = regex;
+    commands::application::switch_to_normal_mode(app);
+    commands::view::scroll_to_cursor(app);
+}
+
+/// Moves the cursor to and tracks the first match at or after the specified
+/// position, returning to normal mode if there are no matches remaining.
+fn select_next_match(app: &mut Application, position: Position) {
+    let mut found = false;
+
+    if let Mode::ReplaceConfirm(ref mut mode) = app.mode {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            let data = buffer.data();
+            let next_match = search::find_all_replacements(&data,
+                                                           &mode.query,
+                                                           &mode.replacement,
+                                                           mode.regex)
+                                 .into_iter()
+                                 .find(|&(ref range, _)| range.start() >= position);
+
+            if let Some((range, replacement)) = next_match {
+                buffer.cursor.move_to(range.start());
+                mode.current_match = Some((range, replacement));
+                found = true;
+            }
+        }
+    }
+
+    if found {
+        commands::view::scroll_cursor_to_center(app);
+    } else {
+        commands::application::switch_to_normal_mode(app);
+    }
+}
+
+fn replace(buffer: &mut Buffer, range: &Range, replacement: &str) {
+    buffer.delete_range(range.clone());
+    buffer.cursor.move_to(range.start());
+    buffer.insert(replacement);
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+    use models::application::{Application, Mode};
+
+    #[test]
+    fn replace_all_replaces_every_match_as_a_single_operation() {
+        let mut app = set_up_application("amp editor\nedit\nedit");
+        set_input(&mut app, "ed", "ud");
+
+        commands::replace::replace_all(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "amp uditor\nudit\nudit");
+
+        // Ensure that we're in normal mode.
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+
+        // Ensure that a single undo reverts all replacements.
+        commands::buffer::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "amp editor\nedit\nedit");
+    }
+
+    #[test]
+    fn accept_input_advances_to_replacement_and_then_to_the_first_match() {
+        let mut app = set_up_application("amp editor\nedit");
+        commands::application::switch_to_replace_insert_mode(&mut app);
+        match app.mode {
+            Mode::ReplaceInsert(ref mut mode) => mode.query = "edit".to_string(),
+            _ => (),
+        };
+
+        // The first acceptance moves on to the replacement.
+        commands::replace::accept_input(&mut app);
+        assert!(match app.mode {
+            Mode::ReplaceInsert(ref mode) => mode.replacement.is_some(),
+            _ => false,
+        });
+
+        // The second starts confirming matches.
+        commands::replace::accept_input(&mut app);
+        assert!(match app.mode {
+            Mode::ReplaceConfirm(_) => true,
+            _ => false,
+        });
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 0,
+                       offset: 4,
+                   });
+    }
+
+    #[test]
+    fn confirming_matches_replaces_and_skips_them_individually() {
+        let mut app = set_up_application("edit\nedit\nedit");
+        set_input(&mut app, "edit", "amp");
+        commands::replace::accept_input(&mut app);
+
+        commands::replace::replace_current_match(&mut app);
+        commands::replace::skip_current_match(&mut app);
+        commands::replace::replace_current_match(&mut app);
+
+        // We've run out of matches, so we should be back in normal mode.
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "amp\nedit\namp");
+
+        // Ensure that a single undo reverts all replacements.
+        commands::buffer::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "edit\nedit\nedit");
+    }
+
+    #[test]
+    fn replace_all_expands_capture_groups_when_using_regular_expressions() {
+        let mut app = set_up_application("amp editor\nedit");
+        set_input(&mut app, r"e(\w)(\w)", "$2$1");
+        commands::search::toggle_regex(&mut app);
+
+        commands::replace::replace_all(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(),
+                   "amp idtor\nidt");
+    }
+
+    #[test]
+    fn replace_all_reports_invalid_patterns() {
+        let mut app = set_up_application("amp editor");
+        set_input(&mut app, "(ed", "ud");
+        commands::search::toggle_regex(&mut app);
+
+        commands::replace::replace_all(&mut app);
+        assert!(match app.mode {
+            Mode::ReplaceInsert(ref mode) => mode.error.is_some(),
+            _ => false,
+        });
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp editor");
+    }
+
+    fn set_up_application(content: &str) -> Application {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert(content);
+        app.workspace.add_buffer(buffer);
+
+        app
+    }
+
+    fn set_input(app: &mut Application, query: &str, replacement: &str) {
+        commands::application::switch_to_replace_insert_mode(app);
+        match app.mode {
+            Mode::ReplaceInsert(ref mut mode) => {
+                mode.query = query.to_string();
+                mode.replacement = Some(replacement.to_string());
+            }
+            _ => (),
+        };
+    }
+}
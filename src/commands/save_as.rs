@@ -0,0 +1,181 @@
+use commands;
+use std::mem;
+use models::application::{Application, Mode};
+
+/// Assigns the input path to the current buffer and saves it, staying in
+/// save-as mode with an explanation if the buffer can't be saved. Existing
+/// files are only overwritten once the path has been accepted a second time.
+pub fn accept_path(app: &mut Application) {
+    let workspace_path = app.workspace.path.clone();
+    let path = match app.mode {
+        Mode::SaveAs(ref mut mode) => {
+            if mode.input.trim().is_empty() {
+                mode.error = Some("Enter a path to save to.".to_string());
+                return;
+            }
+
+            // Relative paths are resolved against the workspace, as they are
+            // when completing them, and paths within it are kept relative.
+            let path = workspace_path.join(mode.input.trim());
+            let relative_path = path.strip_prefix(&workspace_path)
+                                    .ok()
+                                    .map(|relative_path| relative_path.to_path_buf());
+
+            relative_path.unwrap_or(path)
+        }
+        _ => return,
+    };
+
+    // Ask before overwriting a file other than the buffer's own.
+    let overwriting = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            buffer.path.as_ref() != Some(&path) && workspace_path.join(&path).exists()
+        }
+        None => return,
+    };
+    if let Mode::SaveAs(ref mut mode) = app.mode {
+        if overwriting && !mode.confirming_overwrite {
+            mode.confirming_overwrite = true;
+            return;
+        }
+    }
+
+    let (original_path, buffer_id) = match app.workspace.current_buffer() {
+        Some(buffer) => (mem::replace(&mut buffer.path, Some(path)), buffer.id),
+        None => return,
+    };
+
+    match commands::buffer::write(app) {
+        Ok(()) => {
+            // The file's been changed outside of the application;
+            // it'll be saved once we've decided what to do about that.
+            if let Mode::ExternalChange(_) = app.mode {
+                return;
+            }
+
+            // The buffer's unsaved work is safe, under either path.
+            let original_swap_path = match original_path {
+                Some(original_path) => Some(workspace_path.join(original_path)),
+                None => buffer_id.map(|id| app.swap_files.untitled_path(id)),
+            };
+            if let Some(original_swap_path) = original_swap_path {
+                app.swap_files.remove(&original_swap_path);
+            }
+            commands::application::switch_to_normal_mode(app);
+        }
+        Err(error) => {
+            // Restore the original path, since we couldn't save to the new one.
+            if let Some(buffer) = app.workspace.current_buffer() {
+                buffer.path = original_path;
+            }
+            if let Mode::SaveAs(ref mut mode) = app.mode {
+                mode.error = Some(error);
+                mode.confirming_overwrite = false;
+            }
+        }
+    }
+}
+
+/// Completes the input path using the contents of the directory it refers to.
+pub fn complete_path(app: &mut Application) {
+    if let Mode::SaveAs(ref mut mode) = app.mode {
+        mode.complete(&app.workspace.path);
+        mode.error = None;
+        mode.confirming_overwrite = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::{Read, Write};
+    use models::application::Mode;
+    use scribe::Buffer;
+
+    #[test]
+    fn accept_path_assigns_the_path_to_the_buffer_and_saves_it() {
+        let path = env::temp_dir().join("amp_save_as_test.txt");
+        let _ = fs::remove_file(&path);
+
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_save_as_mode(&mut app);
+        match app.mode {
+            Mode::SaveAs(ref mut mode) => mode.input = path.to_string_lossy().into_owned(),
+            _ => (),
+        };
+        commands::save_as::accept_path(&mut app);
+
+        // Ensure that we're back in normal mode, with the buffer saved to the path.
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+        assert_eq!(app.workspace.current_buffer().unwrap().path, Some(path.clone()));
+        let mut data = String::new();
+        File::open(&path).unwrap().read_to_string(&mut data).unwrap();
+        assert_eq!(data, "amp\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn accept_path_asks_before_overwriting_an_existing_file() {
+        let path = env::temp_dir().join("amp_save_as_overwrite_test.txt");
+        File::create(&path).unwrap().write_all(b"editor").unwrap();
+
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::switch_to_save_as_mode(&mut app);
+        match app.mode {
+            Mode::SaveAs(ref mut mode) => mode.input = path.to_string_lossy().into_owned(),
+            _ => (),
+        };
+
+        // The file is left alone until the path is accepted again.
+        commands::save_as::accept_path(&mut app);
+        assert!(match app.mode {
+            Mode::SaveAs(ref mode) => mode.confirming_overwrite,
+            _ => false,
+        });
+        assert_eq!(app.workspace.current_buffer().unwrap().path, None);
+
+        commands::save_as::accept_path(&mut app);
+        let mut data = String::new();
+        File::open(&path).unwrap().read_to_string(&mut data).unwrap();
+        assert_eq!(data, "amp\n");
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn accept_path_keeps_the_prompt_open_when_saving_fails() {
+        let mut app = ::models::application::new();
+        app.workspace.add_buffer(Buffer::new());
+
+        commands::application::switch_to_save_as_mode(&mut app);
+        match app.mode {
+            Mode::SaveAs(ref mut mode) => {
+                mode.input = "amp_non_existent_directory/file.txt".to_string()
+            }
+            _ => (),
+        };
+        commands::save_as::accept_path(&mut app);
+
+        assert!(match app.mode {
+            Mode::SaveAs(ref mode) => mode.error.is_some(),
+            _ => false,
+        });
+        assert_eq!(app.workspace.current_buffer().unwrap().path, None);
+    }
+}
@@ -1,5 +1,10 @@
+extern crate scribe;
+
 use commands;
+use helpers::search;
 use models::application::{Application, Mode};
+use scribe::Buffer;
+use scribe::buffer::Position;
 
 pub fn move_to_previous_result(app: &mut Application) {
     let mut moved = false;
@@ -8,7 +13,7 @@ pub fn move_to_previous_result(app: &mut Application) {
         Some(ref query) => {
             match app.workspace.current_buffer() {
                 Some(buffer) => {
-                    let positions = buffer.search(query);
+                    let positions = result_positions(buffer, query, app.search_regex);
                     for position in positions.iter().rev() {
                         if position < &*buffer.cursor {
                             buffer.cursor.move_to(position.clone());
@@ -49,7 +54,7 @@ pub fn move_to_next_result(app: &mut Application) {
         Some(ref query) => {
             match app.workspace.current_buffer() {
                 Some(buffer) => {
-                    let positions = buffer.search(query);
+                    let positions = result_positions(buffer, query, app.search_regex);
 
                     // Try to find a result after the cursor.
                     for position in positions.iter() {
@@ -87,17 +92,118 @@ pub fn move_to_next_result(app: &mut Application) {
 
 pub fn accept_query(app: &mut Application) {
     let query = match app.mode {
-        Mode::SearchInsert(ref mode) => Some(mode.input.clone()),
+        Mode::SearchInsert(ref mut mode) => {
+            // Don't leave search mode with a query we can't use.
+            if mode.regex {
+                if let Err(error) = search::compile(&mode.input) {
+                    mode.error = Some(error);
+                    return;
+                }
+            }
+
+            Some((mode.input.clone(), mode.regex, mode.original_position))
+        }
         _ => None,
     };
 
-    if query.is_some() {
+    if let Some((query, regex, original_position)) = query {
         commands::application::switch_to_normal_mode(app);
-        app.search_query = query;
+        app.search_query = Some(query);
+        app.search_regex = regex;
+
+        // The cursor may have been moved while typing; look
+        // for the result that follows where the search started.
+        if let Some(buffer) = app.workspace.current_buffer() {
+            buffer.cursor.move_to(original_position);
+        }
         move_to_next_result(app);
     }
 }
 
+/// Moves the cursor to the result that follows where the search started,
+/// updating it as the query is typed. If there aren't any results, the
+/// cursor and view are returned to where they were.
+pub fn preview_result(app: &mut Application) {
+    let mut found = false;
+
+    if let Mode::SearchInsert(ref mut mode) = app.mode {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            let results = search::find_all(&buffer.data(), &mode.input, mode.regex);
+
+            // Wrap to the first result if there's nothing after the original position.
+            let result = results.iter()
+                                .find(|range| range.start() > mode.original_position)
+                                .or(results.first())
+                                .cloned();
+
+            match result {
+                Some(range) => {
+                    buffer.cursor.move_to(range.start());
+                    found = true;
+                    mode.current_result = Some(range);
+                }
+                None => {
+                    buffer.cursor.move_to(mode.original_position);
+                    app.view.scroll_to(buffer, mode.original_line_offset);
+                    mode.current_result = None;
+                }
+            }
+        }
+    }
+
+    if found {
+        commands::view::scroll_cursor_to_center(app);
+    }
+}
+
+/// Abandons the search, returning the cursor and view to where they were.
+pub fn cancel(app: &mut Application) {
+    if let Mode::SearchInsert(ref mode) = app.mode {
+        if let Some(buffer) = app.workspace.current_buffer() {
+            buffer.cursor.move_to(mode.original_position);
+            app.view.scroll_to(buffer, mode.original_line_offset);
+        }
+    }
+
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Switches the query being entered between plain
+/// text and regular expression matching.
+pub fn toggle_regex(app: &mut Application) {
+    match app.mode {
+        Mode::SearchInsert(ref mut mode) => {
+            mode.regex = !mode.regex;
+            mode.error = None;
+        }
+        Mode::ReplaceInsert(ref mut mode) => {
+            mode.regex = !mode.regex;
+            mode.error = None;
+        }
+        Mode::ProjectReplaceInsert(ref mut mode) => {
+            mode.regex = !mode.regex;
+            mode.error = None;
+        }
+        Mode::ProjectSearch(ref mut mode) => {
+            mode.regex = !mode.regex;
+            mode.error = None;
+            mode.results = None;
+        }
+        _ => (),
+    }
+
+    // The results may have changed along with the matching.
+    preview_result(app);
+}
+
+/// Finds the start of every result for the query in the buffer.
+fn result_positions(buffer: &Buffer, query: &str, regex: bool) -> Vec<Position> {
+    search::find_all(&buffer.data(), query, regex)
+        .iter()
+        .map(|range| range.start())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     extern crate scribe;
@@ -240,4 +346,113 @@ mod tests {
                        offset: 4,
                    });
     }
+
+    #[test]
+    fn move_to_next_result_matches_regular_expressions() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\nedit\nedit");
+        app.workspace.add_buffer(buffer);
+
+        // Search for an edit at the start of a line.
+        app.search_query = Some("^ed".to_string());
+        app.search_regex = true;
+        commands::search::move_to_next_result(&mut app);
+
+        // Ensure the buffer cursor is at the expected position.
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 1,
+                       offset: 0,
+                   });
+    }
+
+    #[test]
+    fn accept_query_keeps_search_mode_open_with_an_error_for_invalid_patterns() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor");
+        app.workspace.add_buffer(buffer);
+
+        // Enter search mode, switch to regex matching, and add an invalid pattern.
+        commands::application::switch_to_search_insert_mode(&mut app);
+        commands::search::toggle_regex(&mut app);
+        match app.mode {
+            Mode::SearchInsert(ref mut mode) => mode.input = "(ed".to_string(),
+            _ => (),
+        };
+        commands::search::accept_query(&mut app);
+
+        // Ensure that we're still searching, with an error to show.
+        assert!(match app.mode {
+            Mode::SearchInsert(ref mode) => mode.error.is_some(),
+            _ => false,
+        });
+        assert_eq!(app.search_query, None);
+    }
+
+    #[test]
+    fn preview_result_moves_to_the_result_after_the_original_position() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\nedit\nedit");
+        app.workspace.add_buffer(buffer);
+        app.workspace.current_buffer().unwrap().cursor.move_to(Position {
+            line: 1,
+            offset: 0,
+        });
+
+        // Enter search mode and type a query, one character at a time.
+        commands::application::switch_to_search_insert_mode(&mut app);
+        for character in "ed".chars() {
+            match app.mode {
+                Mode::SearchInsert(ref mut mode) => mode.input.push(character),
+                _ => (),
+            };
+            commands::search::preview_result(&mut app);
+        }
+
+        // Ensure the buffer cursor is at the expected position.
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 2,
+                       offset: 0,
+                   });
+
+        // Ensure that accepting the query doesn't skip ahead.
+        commands::search::accept_query(&mut app);
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 2,
+                       offset: 0,
+                   });
+    }
+
+    #[test]
+    fn cancel_restores_the_original_cursor_position() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp editor\nedit\nedit");
+        app.workspace.add_buffer(buffer);
+
+        // Enter search mode and move to a result.
+        commands::application::switch_to_search_insert_mode(&mut app);
+        match app.mode {
+            Mode::SearchInsert(ref mut mode) => mode.input = "ed".to_string(),
+            _ => (),
+        };
+        commands::search::preview_result(&mut app);
+        commands::search::cancel(&mut app);
+
+        // Ensure that we're in normal mode, where we started.
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+        assert_eq!(*app.workspace.current_buffer().unwrap().cursor,
+                   Position {
+                       line: 0,
+                       offset: 0,
+                   });
+    }
 }
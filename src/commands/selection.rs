@@ -1,6 +1,6 @@
 extern crate scribe;
 
-use models::application::{Application, ClipboardContent, Mode};
+use models::application::{Application, ClipboardContent, Message, Mode};
 use scribe::buffer::{LineRange, Range};
 use super::application;
 use commands;
@@ -26,6 +26,7 @@ pub fn delete(app: &mut Application) {
         }
         None => (),
     };
+    commands::buffer::mark_changed(app);
 
     application::switch_to_normal_mode(app);
     commands::view::scroll_to_cursor(app);
@@ -49,7 +50,7 @@ pub fn copy(app: &mut Application) {
 }
 
 fn copy_to_clipboard(app: &mut Application) {
-    match app.workspace.current_buffer() {
+    let result = match app.workspace.current_buffer() {
         Some(buffer) => {
             match app.mode {
                 Mode::Select(ref select_mode) => {
@@ -60,7 +61,7 @@ fn copy_to_clipboard(app: &mut Application) {
                         Some(selected_data) => {
                             app.clipboard.set_content(ClipboardContent::Inline(selected_data))
                         }
-                        None => (),
+                        None => Ok(()),
                     }
                 }
                 Mode::SelectLine(ref mode) => {
@@ -73,12 +74,18 @@ fn copy_to_clipboard(app: &mut Application) {
                         Some(selected_data) => {
                             app.clipboard.set_content(ClipboardContent::Block(selected_data))
                         }
-                        None => (),
+                        None => Ok(()),
                     }
                 }
-                _ => (),
-            };
+                _ => Ok(()),
+            }
         }
-        None => (),
+        None => Ok(()),
     };
+
+    // The content is still available in-app if the system clipboard can't be updated.
+    if let Err(error) = result {
+        app.messages.push(Message::error(format!("Couldn't copy to the system clipboard: {}",
+                                                 error)));
+    }
 }
@@ -0,0 +1,23 @@
+use models::application::{Application, Message};
+use models::application::session::Session;
+
+/// Saves the open buffers, their positions, the search, and the clipboard,
+/// so that they can be restored the next time the workspace is opened.
+pub fn save(app: &mut Application) {
+    let session = Session::capture(app);
+    if let Err(error) = session.save(&app.workspace.path) {
+        app.messages.push(Message::error(format!("Couldn't save the session: {}", error)));
+    }
+}
+
+/// Restores the workspace's last session, if there is one.
+pub fn restore(app: &mut Application) {
+    match Session::load(&app.workspace.path) {
+        Ok(Some(session)) => session.restore(app),
+        Ok(None) => (),
+        Err(error) => {
+            app.messages.push(Message::error(format!("Couldn't restore the session: {}",
+                                                     error)));
+        }
+    }
+}
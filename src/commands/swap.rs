@@ -0,0 +1,170 @@
+extern crate scribe;
+
+use commands;
+use helpers;
+use std::fs;
+use std::path::{Path, PathBuf};
+use models::application::{Application, Message, Mode, SwapFiles};
+use models::application::modes::recovery;
+use scribe::Buffer;
+
+/// Writes swap files for buffers with unsaved changes, and removes
+/// those of buffers that no longer have any (e.g. they've been undone).
+pub fn write(app: &mut Application) {
+    let workspace_path = app.workspace.path.clone();
+    let swap_files = &mut app.swap_files;
+    let messages = &mut app.messages;
+
+    helpers::workspace::each_buffer(&mut app.workspace, |buffer| {
+        let path = match swap_file_path(&workspace_path, swap_files, buffer) {
+            Some(path) => path,
+            None => return,
+        };
+
+        if buffer.modified() {
+            if let Err(error) = swap_files.write(&path, &buffer.data()) {
+                messages.push(Message::error(format!("Couldn't write a swap file for {}: {}",
+                                                     path.to_string_lossy(),
+                                                     error)));
+            }
+        } else {
+            swap_files.remove(&path);
+        }
+    });
+}
+
+/// Removes the current buffer's swap file, once its changes have been saved or discarded.
+pub fn remove(app: &mut Application) {
+    let workspace_path = app.workspace.path.clone();
+    let path = match app.workspace.current_buffer() {
+        Some(buffer) => swap_file_path(&workspace_path, &app.swap_files, buffer),
+        None => return,
+    };
+
+    if let Some(path) = path {
+        app.swap_files.remove(&path);
+    }
+}
+
+/// The path identifying the buffer's swap file: the absolute path of its
+/// file or, if it doesn't have one yet, a path made up from its ID.
+fn swap_file_path(workspace_path: &Path,
+                  swap_files: &SwapFiles,
+                  buffer: &Buffer)
+                  -> Option<PathBuf> {
+    match buffer.path {
+        Some(ref path) => Some(workspace_path.join(path)),
+        None => buffer.id.map(|id| swap_files.untitled_path(id)),
+    }
+}
+
+/// Offers to recover the current buffer's unsaved work if
+/// a swap file was left behind for it (e.g. by a crash).
+pub fn check(app: &mut Application) {
+    let path = match app.workspace.current_buffer().and_then(|buffer| buffer.path.clone()) {
+        Some(path) => app.workspace.path.join(path),
+        None => return,
+    };
+
+    if let Some(swap_path) = app.swap_files.recoverable(&path) {
+        app.mode = Mode::Recovery(recovery::new(path, swap_path));
+    }
+}
+
+/// Replaces the buffer's content with that of its swap file, leaving it unsaved.
+pub fn recover(app: &mut Application) {
+    let (path, swap_path) = match app.mode {
+        Mode::Recovery(ref mode) => (mode.path.clone(), mode.swap_path.clone()),
+        _ => return,
+    };
+
+    match helpers::read_file(&swap_path) {
+        Ok(data) => {
+            if let Some(buffer) = app.workspace.current_buffer() {
+                helpers::replace_content(buffer, &data);
+            }
+            commands::buffer::mark_changed(app);
+            app.messages.push(Message::info(format!("Recovered {}; save it to keep the changes",
+                                                    path.to_string_lossy())));
+        }
+        Err(error) => {
+            app.messages.push(Message::error(format!("Couldn't recover {}: {}",
+                                                     path.to_string_lossy(),
+                                                     error)));
+        }
+    }
+    commands::application::switch_to_normal_mode(app);
+}
+
+/// Deletes the swap file, leaving the buffer as it is.
+pub fn discard(app: &mut Application) {
+    let swap_path = match app.mode {
+        Mode::Recovery(ref mode) => mode.swap_path.clone(),
+        _ => return,
+    };
+
+    if let Err(error) = fs::remove_file(&swap_path) {
+        app.messages.push(Message::error(format!("Couldn't remove {}: {}",
+                                                 swap_path.to_string_lossy(),
+                                                 error)));
+    }
+    commands::application::switch_to_normal_mode(app);
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use models::application::{Mode, SwapFiles};
+    use scribe::Buffer;
+
+    #[test]
+    fn check_and_recover_restore_the_content_of_a_swap_file() {
+        let directory = env::temp_dir().join("amp_swap_command_test");
+        let path = env::temp_dir().join("amp_swap_command_test.txt");
+        File::create(&path).unwrap().write_all(b"amp\n").unwrap();
+        SwapFiles::new(Some(directory.clone())).write(&path, "amp\neditor\n").unwrap();
+
+        let mut app = ::models::application::new();
+        app.swap_files = SwapFiles::new(Some(directory.clone()));
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\n");
+        buffer.path = Some(path.clone());
+        app.workspace.add_buffer(buffer);
+
+        super::check(&mut app);
+        assert!(match app.mode {
+            Mode::Recovery(_) => true,
+            _ => false,
+        });
+
+        super::recover(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp\neditor\n");
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn write_keeps_swap_files_for_buffers_without_paths() {
+        let directory = env::temp_dir().join("amp_swap_untitled_test");
+        let _ = fs::remove_dir_all(&directory);
+
+        let mut app = ::models::application::new();
+        app.swap_files = SwapFiles::new(Some(directory.clone()));
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+
+        super::write(&mut app);
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+
+        super::remove(&mut app);
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
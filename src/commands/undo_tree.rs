@@ -0,0 +1,258 @@
+extern crate scribe;
+
+use commands;
+use models::application::{Application, Message, Mode, UndoTree};
+use models::application::undo_tree::{self, Change};
+use scribe::Buffer;
+use scribe::buffer::Range;
+
+/// Starts the current buffer's undo tree, if it doesn't have one yet
+/// (see `undo_tree::start`). Buffers added by the application are given
+/// one straight away; this covers any that were added some other way.
+pub fn start(app: &mut Application) {
+    let persistent = app.preferences.persistent_undo();
+    let workspace_path = app.workspace.path.clone();
+    let revision = match commands::buffer::revision(app) {
+        Some((_, revision)) => revision,
+        None => return,
+    };
+
+    let result = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            undo_tree::start(&mut app.undo_trees, buffer, revision, &workspace_path, persistent)
+        }
+        None => return,
+    };
+    if let Err(error) = result {
+        app.messages.push(Message::error(error));
+    }
+}
+
+/// Adds the current buffer's content to its undo tree, if the buffer's been
+/// changed since it was last recorded. This is done once the command group
+/// that changes are made in is closed, so that each group (e.g. everything
+/// typed in a visit to insert mode) becomes a single state.
+pub fn record(app: &mut Application) {
+    let (id, revision) = match commands::buffer::revision(app) {
+        Some(revision) => revision,
+        None => return,
+    };
+    let buffer = match app.workspace.current_buffer() {
+        Some(buffer) => buffer,
+        None => return,
+    };
+
+    if let Some(tree) = app.undo_trees.get_mut(&id) {
+        if tree.revision != revision {
+            tree.record(buffer.data(), *buffer.cursor);
+            tree.revision = revision;
+        }
+    }
+}
+
+/// Returns the current buffer to the state it was changed from.
+pub fn undo(app: &mut Application) {
+    navigate(app, UndoTree::undo);
+}
+
+/// Returns the current buffer to the state most recently undone.
+pub fn redo(app: &mut Application) {
+    navigate(app, UndoTree::redo);
+}
+
+/// Switches the current buffer to the latest state of the next branch of its history.
+pub fn next_branch(app: &mut Application) {
+    navigate(app, UndoTree::next_branch);
+}
+
+/// Switches the current buffer to the latest state of the previous branch of its history.
+pub fn previous_branch(app: &mut Application) {
+    navigate(app, UndoTree::previous_branch);
+}
+
+/// Saves the current buffer's history, if persistent undo is enabled.
+pub fn save(app: &mut Application) {
+    if !app.preferences.persistent_undo() {
+        return;
+    }
+
+    record(app);
+    let workspace_path = app.workspace.path.clone();
+    let buffer = match app.workspace.current_buffer() {
+        Some(buffer) => buffer,
+        None => return,
+    };
+    let tree = match buffer.id {
+        Some(id) => app.undo_trees.get(&id),
+        None => None,
+    };
+
+    if let (Some(ref path), Some(tree)) = (buffer.path.clone(), tree) {
+        if let Err(error) = tree.save(&workspace_path.join(path)) {
+            app.messages.push(Message::error(format!("Couldn't save the history for {}: {}",
+                                                     path.to_string_lossy(),
+                                                     error)));
+        }
+    }
+}
+
+/// Restores the state selected in undo tree mode.
+pub fn restore_selected_state(app: &mut Application) {
+    let index = match app.mode {
+        Mode::UndoTree(ref mode) => mode.selected_index(),
+        _ => None,
+    };
+
+    if let Some(index) = index {
+        navigate(app, |tree| tree.select(index));
+    }
+    commands::application::switch_to_normal_mode(app);
+}
+
+pub fn select_next_state(app: &mut Application) {
+    if let Mode::UndoTree(ref mut mode) = app.mode {
+        mode.states.select_next();
+    }
+}
+
+pub fn select_previous_state(app: &mut Application) {
+    if let Mode::UndoTree(ref mut mode) = app.mode {
+        mode.states.select_previous();
+    }
+}
+
+/// Moves the current buffer's undo tree to another state, and makes the
+/// changes that take the buffer there, as a single operation.
+fn navigate<F>(app: &mut Application, movement: F)
+    where F: FnOnce(&mut UndoTree) -> Vec<Change>
+{
+    // Moving through the history isn't a change that can be repeated.
+    app.macros.discard_change();
+
+    start(app);
+    record(app);
+    let changed = match app.workspace.current_buffer() {
+        Some(buffer) => {
+            let tree = match buffer.id {
+                Some(id) => app.undo_trees.get_mut(&id),
+                None => None,
+            };
+
+            match tree {
+                Some(tree) => {
+                    let changes = movement(tree);
+                    if !changes.is_empty() {
+                        apply(buffer, &changes);
+                        buffer.cursor.move_to(tree.current().cursor);
+                    }
+
+                    !changes.is_empty()
+                }
+                None => false,
+            }
+        }
+        None => false,
+    };
+
+    // The tree is already up to date with the changes it's made.
+    if changed {
+        commands::buffer::mark_changed(app);
+        if let Some((id, revision)) = commands::buffer::revision(app) {
+            if let Some(tree) = app.undo_trees.get_mut(&id) {
+                tree.revision = revision;
+            }
+        }
+    }
+    commands::view::scroll_to_cursor(app);
+}
+
+fn apply(buffer: &mut Buffer, changes: &[Change]) {
+    buffer.start_operation_group();
+    for change in changes {
+        if !change.removed.is_empty() {
+            buffer.delete_range(Range::new(change.position, change.removed_end()));
+        }
+        if !change.inserted.is_empty() {
+            buffer.cursor.move_to(change.position);
+            buffer.insert(&change.inserted);
+        }
+    }
+    buffer.end_operation_group();
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use input;
+    use models::application::Mode;
+    use rustbox::keyboard::Key;
+    use scribe::Buffer;
+
+    #[test]
+    fn undoing_and_making_changes_keeps_the_undone_changes_on_a_branch() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+        super::start(&mut app);
+
+        commands::buffer::insert_newline(&mut app);
+        super::record(&mut app);
+        super::undo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp");
+
+        // Make a different change; redoing no longer brings back the newline.
+        commands::buffer::indent_line(&mut app);
+        super::undo(&mut app);
+        super::redo(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "  amp");
+
+        // The undone change is still a branch away.
+        super::previous_branch(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "\namp");
+        super::next_branch(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "  amp");
+    }
+
+    #[test]
+    fn changes_are_recorded_once_their_command_group_is_closed() {
+        let mut app = ::models::application::new();
+        app.workspace.add_buffer(Buffer::new());
+        let id = app.workspace.current_buffer().unwrap().id.unwrap();
+
+        for character in "iamp".chars() {
+            input::handle(&mut app, Key::Char(character));
+        }
+        assert_eq!(app.undo_trees[&id].states().len(), 1);
+
+        // Everything typed in insert mode is a single state.
+        input::handle(&mut app, Key::Esc);
+        input::handle(&mut app, Key::Char('l'));
+        assert_eq!(app.undo_trees[&id].states().len(), 2);
+        assert_eq!(app.undo_trees[&id].data(), "amp");
+    }
+
+    #[test]
+    fn restore_selected_state_restores_the_state_and_switches_to_normal_mode() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+        super::start(&mut app);
+        commands::buffer::indent_line(&mut app);
+        super::record(&mut app);
+
+        // Select the original state, listed after the newer one.
+        commands::application::switch_to_undo_tree_mode(&mut app);
+        super::select_next_state(&mut app);
+        super::restore_selected_state(&mut app);
+
+        assert_eq!(app.workspace.current_buffer().unwrap().data(), "amp");
+        assert!(match app.mode {
+            Mode::Normal => true,
+            _ => false,
+        });
+    }
+}
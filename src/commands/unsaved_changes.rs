@@ -0,0 +1,109 @@
+use commands;
+use helpers;
+use models::application::{Application, Mode};
+use models::application::modes::unsaved_changes::PendingAction;
+
+/// Saves the listed buffers before carrying on with the pending action. If a
+/// buffer can't be saved, or needs more input (e.g. a path), it's left as the
+/// current buffer, and the action is abandoned so that it can be dealt with.
+pub fn save(app: &mut Application) {
+    let (action, ids) = match app.mode {
+        Mode::UnsavedChanges(ref mode) => {
+            (mode.action, mode.buffers.iter().map(|entry| entry.id).collect::<Vec<usize>>())
+        }
+        _ => return,
+    };
+    let current_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+
+    for id in ids {
+        if !helpers::workspace::select_buffer(&mut app.workspace, id) {
+            continue;
+        }
+
+        commands::buffer::save(app);
+        let saved = app.workspace.current_buffer().map(|buffer| !buffer.modified());
+        match app.mode {
+            Mode::UnsavedChanges(ref mut mode) => {
+                if saved == Some(true) {
+                    mode.buffers.retain(|entry| entry.id != id);
+                } else {
+                    return;
+                }
+            }
+            // Saving the buffer needs more input; let the new mode collect it.
+            _ => return,
+        }
+    }
+
+    if let Some(id) = current_id {
+        helpers::workspace::select_buffer(&mut app.workspace, id);
+    }
+    perform(app, action);
+}
+
+/// Carries on with the pending action, discarding the listed buffers' changes.
+pub fn discard(app: &mut Application) {
+    let action = match app.mode {
+        Mode::UnsavedChanges(ref mode) => mode.action,
+        _ => return,
+    };
+
+    perform(app, action);
+}
+
+fn perform(app: &mut Application, action: PendingAction) {
+    match action {
+        PendingAction::CloseBuffer => {
+            commands::buffer::close_without_saving(app);
+            commands::application::switch_to_normal_mode(app);
+        }
+        PendingAction::Exit => app.mode = Mode::Exit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use commands;
+    use models::application::Mode;
+    use scribe::Buffer;
+
+    #[test]
+    fn close_asks_before_discarding_unsaved_changes() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::buffer::close(&mut app);
+        assert!(app.workspace.current_buffer().is_some());
+        assert!(match app.mode {
+            Mode::UnsavedChanges(ref mode) => mode.buffers.len() == 1,
+            _ => false,
+        });
+
+        super::discard(&mut app);
+        assert!(app.workspace.current_buffer().is_none());
+    }
+
+    #[test]
+    fn exit_asks_before_discarding_unsaved_changes() {
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        app.workspace.add_buffer(buffer);
+
+        commands::application::exit(&mut app);
+        assert!(match app.mode {
+            Mode::UnsavedChanges(_) => true,
+            _ => false,
+        });
+
+        super::discard(&mut app);
+        assert!(match app.mode {
+            Mode::Exit => true,
+            _ => false,
+        });
+    }
+}
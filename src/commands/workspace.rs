@@ -1,5 +1,18 @@
-use models::application::Application;
+use helpers;
+use models::application::{Application, Message};
 
 pub fn next_buffer(app: &mut Application) {
     app.workspace.next_buffer();
 }
+
+/// Switches back to the buffer that was current before this one.
+pub fn switch_to_previous_buffer(app: &mut Application) {
+    let found = match app.previous_buffer_id {
+        Some(id) => helpers::workspace::select_buffer(&mut app.workspace, id),
+        None => false,
+    };
+
+    if !found {
+        app.messages.push(Message::warning("There's no previous buffer to switch to."));
+    }
+}
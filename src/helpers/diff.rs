@@ -0,0 +1,125 @@
+use std::cmp;
+
+/// The largest table of line comparisons built for a diff. Changes spanning
+/// more lines than that are shown as replacing the original lines outright.
+const MAX_COMPARISONS: usize = 1000000;
+
+/// A line in a diff, describing how it differs between two versions of a file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Compares two versions of a file, line by line, describing the changes
+/// needed to turn the original into the modified version. Lines common to
+/// the start and end of both are set aside before comparing the rest, which
+/// keeps the (quadratic) comparison small for the usual, localized edits.
+pub fn diff(original: &str, modified: &str) -> Vec<DiffLine> {
+    let original_lines: Vec<&str> = original.lines().collect();
+    let modified_lines: Vec<&str> = modified.lines().collect();
+
+    let prefix_length = original_lines.iter()
+                                      .zip(modified_lines.iter())
+                                      .take_while(|&(a, b)| a == b)
+                                      .count();
+    let suffix_length = original_lines[prefix_length..]
+                            .iter()
+                            .rev()
+                            .zip(modified_lines[prefix_length..].iter().rev())
+                            .take_while(|&(a, b)| a == b)
+                            .count();
+    let original_changes = &original_lines[prefix_length..original_lines.len() - suffix_length];
+    let modified_changes = &modified_lines[prefix_length..modified_lines.len() - suffix_length];
+
+    let mut lines: Vec<DiffLine> = original_lines[..prefix_length]
+                                       .iter()
+                                       .map(|line| DiffLine::Unchanged(line.to_string()))
+                                       .collect();
+    lines.extend(diff_lines(original_changes, modified_changes));
+    lines.extend(original_lines[original_lines.len() - suffix_length..]
+                     .iter()
+                     .map(|line| DiffLine::Unchanged(line.to_string())));
+
+    lines
+}
+
+/// Diffs two sets of lines using their longest common subsequence.
+fn diff_lines(original: &[&str], modified: &[&str]) -> Vec<DiffLine> {
+    if original.len().saturating_mul(modified.len()) > MAX_COMPARISONS {
+        let mut lines: Vec<DiffLine> = original.iter()
+                                               .map(|line| DiffLine::Removed(line.to_string()))
+                                               .collect();
+        lines.extend(modified.iter().map(|line| DiffLine::Added(line.to_string())));
+
+        return lines;
+    }
+
+    // Find the length of the longest common subsequence
+    // of every pair of suffixes, working backwards.
+    let mut lengths = vec![vec![0; modified.len() + 1]; original.len() + 1];
+    for i in (0..original.len()).rev() {
+        for j in (0..modified.len()).rev() {
+            lengths[i][j] = if original[i] == modified[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                cmp::max(lengths[i + 1][j], lengths[i][j + 1])
+            };
+        }
+    }
+
+    // Walk the table from the start, following the longest subsequence.
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < original.len() && j < modified.len() {
+        if original[i] == modified[j] {
+            lines.push(DiffLine::Unchanged(original[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(DiffLine::Removed(original[i].to_string()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(modified[j].to_string()));
+            j += 1;
+        }
+    }
+    lines.extend(original[i..].iter().map(|line| DiffLine::Removed(line.to_string())));
+    lines.extend(modified[j..].iter().map(|line| DiffLine::Added(line.to_string())));
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, DiffLine};
+
+    #[test]
+    fn diff_describes_added_removed_and_unchanged_lines() {
+        assert_eq!(diff("amp\neditor\nrust\n", "amp\ntext editor\nrust\nterminal\n"),
+                   vec![DiffLine::Unchanged("amp".to_string()),
+                        DiffLine::Removed("editor".to_string()),
+                        DiffLine::Added("text editor".to_string()),
+                        DiffLine::Unchanged("rust".to_string()),
+                        DiffLine::Added("terminal".to_string())]);
+    }
+
+    #[test]
+    fn diff_replaces_lines_outright_when_there_are_too_many_to_compare() {
+        let original: Vec<String> = (0..1001).map(|line| format!("amp {}", line)).collect();
+        let modified: Vec<String> = (0..1001).map(|line| format!("editor {}", line)).collect();
+        let lines = diff(&original.join("\n"), &modified.join("\n"));
+
+        assert_eq!(lines.len(), 2002);
+        assert_eq!(lines[1000], DiffLine::Removed("amp 1000".to_string()));
+        assert_eq!(lines[1001], DiffLine::Added("editor 0".to_string()));
+    }
+
+    #[test]
+    fn diff_handles_identical_content() {
+        assert_eq!(diff("amp\neditor", "amp\neditor"),
+                   vec![DiffLine::Unchanged("amp".to_string()),
+                        DiffLine::Unchanged("editor".to_string())]);
+    }
+}
@@ -0,0 +1,49 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Where user-level configuration files (e.g. the key map) are stored.
+/// Honours XDG_CONFIG_HOME, falling back to ~/.config/amp.
+pub fn config() -> Option<PathBuf> {
+    match env::var("XDG_CONFIG_HOME") {
+        Ok(ref path) if !path.is_empty() => Some(PathBuf::from(path).join("amp")),
+        _ => env::home_dir().map(|home| home.join(".config").join("amp")),
+    }
+}
+
+/// Where files that can be regenerated or discarded (e.g. swap files) are
+/// stored. Honours XDG_CACHE_HOME, falling back to ~/.cache/amp.
+pub fn cache() -> Option<PathBuf> {
+    match env::var("XDG_CACHE_HOME") {
+        Ok(ref path) if !path.is_empty() => Some(PathBuf::from(path).join("amp")),
+        _ => env::home_dir().map(|home| home.join(".cache").join("amp")),
+    }
+}
+
+/// Where data that should persist between runs (e.g. sessions) is stored.
+/// Honours XDG_DATA_HOME, falling back to ~/.local/share/amp.
+pub fn data() -> Option<PathBuf> {
+    match env::var("XDG_DATA_HOME") {
+        Ok(ref path) if !path.is_empty() => Some(PathBuf::from(path).join("amp")),
+        _ => env::home_dir().map(|home| home.join(".local").join("share").join("amp")),
+    }
+}
+
+/// Flattens a path into a file name, by percent-encoding its separators
+/// (e.g. "/home/amp/notes.txt" becomes "%2Fhome%2Famp%2Fnotes.txt"). Percent
+/// signs are encoded too, so that different paths never share a file name.
+pub fn file_name_for(path: &Path) -> String {
+    path.to_string_lossy().replace('%', "%25").replace('/', "%2F").replace('\\', "%5C")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use super::file_name_for;
+
+    #[test]
+    fn file_name_for_keeps_paths_with_percent_signs_apart() {
+        assert_eq!(file_name_for(Path::new("/amp/notes.txt")), "%2Famp%2Fnotes.txt");
+        assert!(file_name_for(Path::new("amp%2Fnotes.txt")) !=
+                file_name_for(Path::new("amp/notes.txt")));
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::iter;
+
+/// The widest indentation level we'll infer from space-indented content.
+const MAX_DETECTED_WIDTH: usize = 8;
+
+/// A style of indentation, used for a single level of indentation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Indentation {
+    Tabs,
+    Spaces(usize),
+}
+
+impl Indentation {
+    /// The content inserted for a single level of indentation.
+    pub fn unit(&self) -> String {
+        match *self {
+            Indentation::Tabs => "\t".to_string(),
+            Indentation::Spaces(width) => iter::repeat(' ').take(width).collect(),
+        }
+    }
+
+    /// Expresses leading whitespace using this style of indentation,
+    /// treating tabs as spanning the specified number of columns.
+    pub fn convert(&self, whitespace: &str, tab_width: usize) -> String {
+        let columns = whitespace.chars().fold(0, |columns, character| {
+            match character {
+                '\t' => columns + tab_width,
+                _ => columns + 1,
+            }
+        });
+
+        match *self {
+            Indentation::Tabs => {
+                let mut content: String = iter::repeat('\t').take(columns / tab_width).collect();
+                content.extend(iter::repeat(' ').take(columns % tab_width));
+                content
+            }
+            Indentation::Spaces(_) => iter::repeat(' ').take(columns).collect(),
+        }
+    }
+
+    /// The number of leading characters removed from the
+    /// line when outdenting it by a single level.
+    pub fn outdent_length(&self, line: &str) -> usize {
+        match *self {
+            Indentation::Tabs if line.starts_with('\t') => 1,
+            Indentation::Tabs => 0,
+            Indentation::Spaces(width) => {
+                line.chars().take(width).take_while(|&c| c == ' ').count()
+            }
+        }
+    }
+}
+
+/// Infers the indentation style used by the specified content, based on the
+/// leading whitespace of its lines and how it grows from one line to the next.
+/// Returns None if the content isn't indented enough to tell.
+pub fn detect(data: &str) -> Option<Indentation> {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut widths: HashMap<usize, usize> = HashMap::new();
+    let mut previous_indent = 0;
+
+    for line in data.lines() {
+        // Blank lines don't say anything about indentation.
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.starts_with('\t') {
+            tab_lines += 1;
+            previous_indent = 0;
+            continue;
+        }
+
+        // A single leading space is more likely to be alignment
+        // (e.g. within a block comment) than indentation.
+        let indent = line.chars().take_while(|&c| c == ' ').count();
+        if indent > 1 {
+            space_lines += 1;
+
+            if indent > previous_indent && indent - previous_indent <= MAX_DETECTED_WIDTH {
+                *widths.entry(indent - previous_indent).or_insert(0) += 1;
+            }
+        }
+        previous_indent = indent;
+    }
+
+    if tab_lines > space_lines {
+        return Some(Indentation::Tabs);
+    }
+
+    // Use the most common increase in indentation, preferring narrower widths.
+    let mut detected_width = None;
+    let mut detected_count = 0;
+    for width in 2..MAX_DETECTED_WIDTH + 1 {
+        if let Some(&count) = widths.get(&width) {
+            if count > detected_count {
+                detected_width = Some(width);
+                detected_count = count;
+            }
+        }
+    }
+
+    detected_width.map(|width| Indentation::Spaces(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect, Indentation};
+
+    #[test]
+    fn detect_recognizes_tab_indentation() {
+        let data = "fn main() {\n\tif true {\n\t\tprintln!(\"amp\");\n\t}\n}\n";
+
+        assert_eq!(detect(data), Some(Indentation::Tabs));
+    }
+
+    #[test]
+    fn detect_uses_the_most_common_space_indentation_width() {
+        let data = "class Editor:\n    def run(self):\n        if True:\n            pass\n\n    \
+                    def stop(self, force,\n                   quietly):\n        pass\n";
+
+        assert_eq!(detect(data), Some(Indentation::Spaces(4)));
+    }
+
+    #[test]
+    fn detect_ignores_single_space_alignment() {
+        let data = "/**\n * amp\n */\nfn main() {\n  amp();\n}\n";
+
+        assert_eq!(detect(data), Some(Indentation::Spaces(2)));
+    }
+
+    #[test]
+    fn detect_returns_none_for_unindented_content() {
+        assert_eq!(detect("amp\neditor\n"), None);
+        assert_eq!(detect(""), None);
+    }
+
+    #[test]
+    fn convert_preserves_the_width_of_leading_whitespace() {
+        assert_eq!(Indentation::Tabs.convert("     ", 4), "\t ");
+        assert_eq!(Indentation::Tabs.convert("\t", 4), "\t");
+        assert_eq!(Indentation::Spaces(2).convert("\t  ", 4), "      ");
+        assert_eq!(Indentation::Spaces(2).convert("", 4), "");
+    }
+
+    #[test]
+    fn outdent_length_is_limited_to_a_single_level() {
+        assert_eq!(Indentation::Spaces(2).outdent_length("     amp"), 2);
+        assert_eq!(Indentation::Spaces(4).outdent_length("  amp"), 2);
+        assert_eq!(Indentation::Tabs.outdent_length("\t\tamp"), 1);
+        assert_eq!(Indentation::Tabs.outdent_length("  amp"), 0);
+    }
+}
@@ -2,10 +2,18 @@ extern crate scribe;
 
 pub use self::selectable_set::SelectableSet;
 
+pub mod diff;
+pub mod directories;
+pub mod indentation;
 pub mod movement_lexer;
+pub mod search;
 mod selectable_set;
 pub mod token;
+pub mod workspace;
 
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
 use scribe::buffer::{Buffer, LineRange, Position, Range};
 
 /// Translates a line range to a regular range, including its last line.
@@ -49,6 +57,47 @@ pub fn inclusive_range(line_range: &LineRange, buffer: &mut Buffer) -> Range {
                end_position)
 }
 
+/// Reads the file's content into a string.
+pub fn read_file(path: &Path) -> io::Result<String> {
+    let mut data = String::new();
+    try!(File::open(path).and_then(|mut file| file.read_to_string(&mut data)));
+
+    Ok(data)
+}
+
+/// Swaps the buffer's content, keeping the cursor where it was, if it still fits.
+pub fn replace_content(buffer: &mut Buffer, data: &str) {
+    let original_position = *buffer.cursor;
+
+    // Find the end of the buffer, using the last line's length.
+    let current_data = buffer.data();
+    let line_count = current_data.split('\n').count();
+    let end = Position {
+        line: line_count - 1,
+        offset: current_data.split('\n').last().map(|line| line.chars().count()).unwrap_or(0),
+    };
+
+    buffer.start_operation_group();
+    buffer.delete_range(Range::new(Position {
+                                       line: 0,
+                                       offset: 0,
+                                   },
+                                   end));
+    buffer.cursor.move_to(Position {
+        line: 0,
+        offset: 0,
+    });
+    buffer.insert(data);
+    buffer.end_operation_group();
+
+    if !buffer.cursor.move_to(original_position) {
+        buffer.cursor.move_to(Position {
+            line: 0,
+            offset: 0,
+        });
+    }
+}
+
 /// Produce a nested chain of if-lets and ifs from the patterns:
 /// Pilfered from:
 /// https://github.com/Manishearth/rust-clippy/blob/master/src/utils.rs
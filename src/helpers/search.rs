@@ -0,0 +1,236 @@
+use regex::{Captures, Regex};
+use scribe::buffer::{Position, Range};
+
+/// Finds all occurrences of the query in the specified data, returning
+/// their ranges. Queries don't span lines, so matching is done one line
+/// at a time, with offsets measured in characters rather than bytes.
+pub fn find(data: &str, query: &str) -> Vec<Range> {
+    let mut ranges = Vec::new();
+    if query.is_empty() {
+        return ranges;
+    }
+
+    let query_length = query.chars().count();
+    for (line_number, line) in data.split('\n').enumerate() {
+        for (index, _) in line.match_indices(query) {
+            let offset = line[..index].chars().count();
+            ranges.push(Range::new(Position {
+                                       line: line_number,
+                                       offset: offset,
+                                   },
+                                   Position {
+                                       line: line_number,
+                                       offset: offset + query_length,
+                                   }));
+        }
+    }
+
+    ranges
+}
+
+/// Compiles a regular expression search query, describing
+/// the problem in a displayable form if it's invalid.
+pub fn compile(query: &str) -> Result<Regex, String> {
+    Regex::new(query).map_err(|error| format!("Invalid pattern: {}", error))
+}
+
+/// Finds all results for a search query, matching it as a regular
+/// expression if specified. Invalid patterns have no results.
+pub fn find_all(data: &str, query: &str, regex: bool) -> Vec<Range> {
+    if regex {
+        match compile(query) {
+            Ok(pattern) => find_pattern(data, &pattern),
+            Err(_) => Vec::new(),
+        }
+    } else {
+        find(data, query)
+    }
+}
+
+/// Like `find_all`, but pairs each result with its replacement,
+/// which can refer to capture groups when using regular expressions.
+pub fn find_all_replacements(data: &str,
+                             query: &str,
+                             replacement: &str,
+                             regex: bool)
+                             -> Vec<(Range, String)> {
+    if regex {
+        match compile(query) {
+            Ok(pattern) => find_replacements(data, &pattern, replacement),
+            Err(_) => Vec::new(),
+        }
+    } else {
+        find(data, query)
+            .into_iter()
+            .map(|range| (range, replacement.to_string()))
+            .collect()
+    }
+}
+
+/// Like `find`, but matches a regular expression. Empty matches
+/// (e.g. for "^") are ignored, since there's nothing to select.
+pub fn find_pattern(data: &str, pattern: &Regex) -> Vec<Range> {
+    let mut ranges = Vec::new();
+    each_match(data, pattern, |range, _| ranges.push(range));
+
+    ranges
+}
+
+/// Finds matches for a regular expression, pairing each with its replacement.
+/// Replacements can refer to capture groups using "$1", "$2", etc.; "$0" is
+/// the entire match, and "$$" is a literal dollar sign.
+pub fn find_replacements(data: &str, pattern: &Regex, replacement: &str) -> Vec<(Range, String)> {
+    let mut replacements = Vec::new();
+    each_match(data, pattern, |range, captures| {
+        replacements.push((range, expand(captures, replacement)))
+    });
+
+    replacements
+}
+
+fn each_match<F>(data: &str, pattern: &Regex, mut f: F)
+    where F: FnMut(Range, &Captures)
+{
+    for (line_number, line) in data.split('\n').enumerate() {
+        for captures in pattern.captures_iter(line) {
+            let (start, end) = match captures.pos(0) {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            if start == end {
+                continue;
+            }
+
+            let start_offset = line[..start].chars().count();
+            let range = Range::new(Position {
+                                       line: line_number,
+                                       offset: start_offset,
+                                   },
+                                   Position {
+                                       line: line_number,
+                                       offset: start_offset + line[start..end].chars().count(),
+                                   });
+            f(range, &captures);
+        }
+    }
+}
+
+fn expand(captures: &Captures, replacement: &str) -> String {
+    let mut result = String::new();
+    let mut characters = replacement.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        if character != '$' {
+            result.push(character);
+            continue;
+        }
+
+        // Collect the group number, if there is one.
+        let mut group = String::new();
+        while let Some(&digit) = characters.peek() {
+            if !digit.is_digit(10) {
+                break;
+            }
+            group.push(digit);
+            characters.next();
+        }
+
+        if group.is_empty() {
+            // Treat "$$" as an escaped dollar sign, and
+            // leave a dollar sign on its own as-is.
+            if characters.peek() == Some(&'$') {
+                characters.next();
+            }
+            result.push('$');
+        } else if let Ok(index) = group.parse::<usize>() {
+            // Unmatched and non-existent groups expand to nothing.
+            result.push_str(captures.at(index).unwrap_or(""));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use super::{find, find_pattern, find_replacements};
+    use regex::Regex;
+    use scribe::buffer::{Position, Range};
+
+    #[test]
+    fn find_returns_the_range_of_each_match() {
+        assert_eq!(find("amp editor\nedit", "ed"),
+                   vec![Range::new(Position {
+                                       line: 0,
+                                       offset: 4,
+                                   },
+                                   Position {
+                                       line: 0,
+                                       offset: 6,
+                                   }),
+                        Range::new(Position {
+                                       line: 1,
+                                       offset: 0,
+                                   },
+                                   Position {
+                                       line: 1,
+                                       offset: 2,
+                                   })]);
+    }
+
+    #[test]
+    fn find_uses_character_offsets() {
+        assert_eq!(find("éditeur éd", "éd"),
+                   vec![Range::new(Position {
+                                       line: 0,
+                                       offset: 0,
+                                   },
+                                   Position {
+                                       line: 0,
+                                       offset: 2,
+                                   }),
+                        Range::new(Position {
+                                       line: 0,
+                                       offset: 8,
+                                   },
+                                   Position {
+                                       line: 0,
+                                       offset: 10,
+                                   })]);
+    }
+
+    #[test]
+    fn find_returns_nothing_for_empty_queries() {
+        assert!(find("amp", "").is_empty());
+    }
+
+    #[test]
+    fn find_pattern_returns_the_character_range_of_each_match() {
+        let pattern = Regex::new("é+d").unwrap();
+        assert_eq!(find_pattern("amp éédit\ned", &pattern),
+                   vec![Range::new(Position {
+                                       line: 0,
+                                       offset: 4,
+                                   },
+                                   Position {
+                                       line: 0,
+                                       offset: 7,
+                                   })]);
+    }
+
+    #[test]
+    fn find_pattern_ignores_empty_matches() {
+        let pattern = Regex::new("^").unwrap();
+        assert!(find_pattern("amp\neditor", &pattern).is_empty());
+    }
+
+    #[test]
+    fn find_replacements_expands_capture_groups() {
+        let pattern = Regex::new(r"(\w+)::(\w+)").unwrap();
+        let replacements = find_replacements("buffer::save", &pattern, "$2 $1 $$3 $0");
+        assert_eq!(replacements.len(), 1);
+        assert_eq!(replacements[0].1, "save buffer $3 buffer::save".to_string());
+    }
+}
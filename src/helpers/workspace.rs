@@ -0,0 +1,87 @@
+use scribe::{Buffer, Workspace};
+
+/// Calls the function with each of the workspace's buffers. The workspace doesn't
+/// expose its buffers directly, so this cycles through them, ending where it started.
+pub fn each_buffer<F>(workspace: &mut Workspace, mut f: F)
+    where F: FnMut(&mut Buffer)
+{
+    let start_id = match workspace.current_buffer() {
+        Some(buffer) => buffer.id,
+        None => return,
+    };
+
+    loop {
+        if let Some(buffer) = workspace.current_buffer() {
+            f(buffer);
+        }
+
+        workspace.next_buffer();
+        let current_id = workspace.current_buffer().map(|buffer| buffer.id);
+        if current_id.is_none() || current_id == Some(start_id) {
+            break;
+        }
+    }
+}
+
+/// Makes the buffer with the specified id the current one, returning whether
+/// it was found. If it wasn't, the current buffer is left as it was.
+pub fn select_buffer(workspace: &mut Workspace, id: usize) -> bool {
+    let mut found = false;
+    each_buffer(workspace, |buffer| found = found || buffer.id == Some(id));
+    if !found {
+        return false;
+    }
+
+    while workspace.current_buffer().and_then(|buffer| buffer.id) != Some(id) {
+        workspace.next_buffer();
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use std::env;
+    use super::{each_buffer, select_buffer};
+    use scribe::{Buffer, Workspace};
+
+    #[test]
+    fn each_buffer_visits_every_buffer_once_and_restores_the_current_buffer() {
+        let mut workspace = Workspace::new(env::current_dir().unwrap());
+        for content in vec!["amp", "editor", "rust"] {
+            let mut buffer = Buffer::new();
+            buffer.insert(content);
+            workspace.add_buffer(buffer);
+        }
+        let current_data = workspace.current_buffer().unwrap().data();
+
+        let mut visited = Vec::new();
+        each_buffer(&mut workspace, |buffer| visited.push(buffer.data()));
+        visited.sort();
+
+        assert_eq!(visited, vec!["amp", "editor", "rust"]);
+        assert_eq!(workspace.current_buffer().unwrap().data(), current_data);
+    }
+
+    #[test]
+    fn select_buffer_makes_the_matching_buffer_current() {
+        let mut workspace = Workspace::new(env::current_dir().unwrap());
+        for content in vec!["amp", "editor"] {
+            let mut buffer = Buffer::new();
+            buffer.insert(content);
+            workspace.add_buffer(buffer);
+        }
+        let first_id = workspace.current_buffer().unwrap().id.unwrap();
+        workspace.next_buffer();
+        let second_id = workspace.current_buffer().unwrap().id.unwrap();
+
+        assert!(select_buffer(&mut workspace, first_id));
+        assert_eq!(workspace.current_buffer().unwrap().id, Some(first_id));
+
+        // Unknown buffers leave the current one selected.
+        assert!(!select_buffer(&mut workspace, first_id + second_id + 1));
+        assert_eq!(workspace.current_buffer().unwrap().id, Some(first_id));
+    }
+}
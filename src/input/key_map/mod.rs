@@ -0,0 +1,309 @@
+extern crate yaml_rust;
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use commands::{self, Command};
+use helpers::directories;
+use rustbox::keyboard::Key;
+use self::yaml_rust::{Yaml, YamlLoader};
+
+const DEFAULT_KEY_MAP: &'static str = include_str!("default.yml");
+const USER_KEY_MAP_FILE: &'static str = "keymap.yml";
+
+/// Maps keys to named commands on a per-mode basis. The built-in bindings
+/// are loaded first, after which the user's key map (if there is one) is
+/// layered on top, replacing any defaults bound to the same key.
+pub struct KeyMap {
+    bindings: HashMap<String, HashMap<String, String>>,
+    commands: HashMap<&'static str, Command>,
+}
+
+impl KeyMap {
+    /// Builds a key map using the default bindings,
+    /// overridden by those in the user's key map file.
+    pub fn load() -> Result<KeyMap, String> {
+        let mut key_map = try!(KeyMap::default());
+
+        if let Some(path) = directories::config().map(|dir| dir.join(USER_KEY_MAP_FILE)) {
+            if path.exists() {
+                let mut data = String::new();
+                let result = File::open(&path).and_then(|mut file| file.read_to_string(&mut data));
+                if let Err(e) = result {
+                    return Err(format!("Couldn't read {}: {}", path.to_string_lossy(), e));
+                }
+
+                try!(key_map.merge(&data));
+            }
+        }
+
+        Ok(key_map)
+    }
+
+    /// Builds a key map using only the default bindings.
+    pub fn default() -> Result<KeyMap, String> {
+        KeyMap::parse(DEFAULT_KEY_MAP)
+    }
+
+    /// Builds a key map using only the bindings in the specified YAML data.
+    pub fn parse(data: &str) -> Result<KeyMap, String> {
+        let mut key_map = KeyMap {
+            bindings: HashMap::new(),
+            commands: commands::hash_map(),
+        };
+        try!(key_map.merge(data));
+
+        Ok(key_map)
+    }
+
+    /// Adds the bindings in the specified YAML data to the key map,
+    /// replacing any existing bindings for the same mode and key.
+    pub fn merge(&mut self, data: &str) -> Result<(), String> {
+        let documents = try!(YamlLoader::load_from_str(data).map_err(|e| e.to_string()));
+        let modes = match documents.first() {
+            Some(&Yaml::Hash(ref modes)) => modes,
+            // An empty key map doesn't change anything.
+            Some(&Yaml::Null) | None => return Ok(()),
+            _ => return Err("Key maps must be structured as a hash of modes.".to_string()),
+        };
+
+        for (mode, bindings) in modes {
+            let mode_name = match mode.as_str() {
+                Some(name) => name,
+                None => return Err("Key map mode names must be strings.".to_string()),
+            };
+            let bindings = match bindings.as_hash() {
+                Some(bindings) => bindings,
+                None => return Err(format!("Bindings for {} mode must be a hash.", mode_name)),
+            };
+
+            for (key, command) in bindings {
+                // YAML will parse numeric keys (e.g. "0") as integers; allow them.
+                let key_value = match *key {
+                    Yaml::String(ref value) => value.clone(),
+                    Yaml::Integer(value) => value.to_string(),
+                    _ => return Err(format!("Invalid key in {} mode bindings.", mode_name)),
+                };
+                let key_name = match parse_key(&key_value).and_then(|key| key_name(&key)) {
+                    Some(name) => name,
+                    None => return Err(format!("Unknown key \"{}\" in {} mode bindings.",
+                                               key_value, mode_name)),
+                };
+                let command_name = match command.as_str() {
+                    Some(name) => name,
+                    None => return Err(format!("Invalid command bound to \"{}\" in {} mode.",
+                                               key_value, mode_name)),
+                };
+                if !self.commands.contains_key(command_name) {
+                    return Err(format!("Unknown command \"{}\" bound to \"{}\" in {} mode.",
+                                       command_name, key_value, mode_name));
+                }
+
+                self.bindings
+                    .entry(mode_name.to_string())
+                    .or_insert(HashMap::new())
+                    .insert(key_name, command_name.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the command bound to the specified key in the given mode, if any.
+    pub fn command_for(&self, mode: &str, key: &Key) -> Option<Command> {
+        let name = match key_name(key) {
+            Some(name) => name,
+            None => return None,
+        };
+
+        match self.bindings.get(mode) {
+            Some(bindings) => {
+                bindings.get(&name)
+                        .and_then(|command| self.commands.get(&command[..]))
+                        .map(|command| *command)
+            }
+            None => None,
+        }
+    }
+
+    /// Returns the names of the modes in which the command is bound to a key.
+    pub fn modes_binding(&self, command: &str) -> Vec<&str> {
+        self.bindings
+            .iter()
+            .filter(|&(_, bindings)| bindings.values().any(|name| name == command))
+            .map(|(mode, _)| &mode[..])
+            .collect()
+    }
+
+    /// Returns the name of the key bound to the specified command in the
+    /// given mode, if any. When several keys are bound to the same command,
+    /// the shortest name is preferred (e.g. "j" over "down").
+    pub fn binding_for(&self, mode: &str, command: &str) -> Option<String> {
+        match self.bindings.get(mode) {
+            Some(bindings) => {
+                let mut keys: Vec<&String> = bindings.iter()
+                                                     .filter(|&(_, name)| name == command)
+                                                     .map(|(key, _)| key)
+                                                     .collect();
+                keys.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+                keys.first().map(|key| key.to_string())
+            }
+            None => None,
+        }
+    }
+}
+
+/// Translates a key name (e.g. "j", "ctrl-j", "enter", "f5") into a key.
+fn parse_key(name: &str) -> Option<Key> {
+    // Single characters map directly to themselves.
+    let mut characters = name.chars();
+    if let (Some(c), None) = (characters.next(), characters.next()) {
+        return Some(Key::Char(c));
+    }
+
+    match name {
+        "space" => Some(Key::Char(' ')),
+        "enter" => Some(Key::Enter),
+        "tab" => Some(Key::Tab),
+        "backspace" => Some(Key::Backspace),
+        "esc" => Some(Key::Esc),
+        "up" => Some(Key::Up),
+        "down" => Some(Key::Down),
+        "left" => Some(Key::Left),
+        "right" => Some(Key::Right),
+        "home" => Some(Key::Home),
+        "end" => Some(Key::End),
+        "page_up" => Some(Key::PageUp),
+        "page_down" => Some(Key::PageDown),
+        "delete" => Some(Key::Delete),
+        "insert" => Some(Key::Insert),
+        _ => {
+            if name.starts_with("ctrl-") {
+                let mut characters = name["ctrl-".len()..].chars();
+                match (characters.next(), characters.next()) {
+                    (Some(c), None) => Some(Key::Ctrl(c)),
+                    _ => None,
+                }
+            } else if name.starts_with('f') {
+                name[1..].parse::<u32>().ok().map(|number| Key::F(number))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// The inverse of parse_key; builds a normalized name for a key.
+fn key_name(key: &Key) -> Option<String> {
+    match *key {
+        Key::Char(' ') => Some("space".to_string()),
+        Key::Char(c) => Some(c.to_string()),
+        Key::Ctrl(c) => Some(format!("ctrl-{}", c)),
+        Key::F(number) => Some(format!("f{}", number)),
+        Key::Enter => Some("enter".to_string()),
+        Key::Tab => Some("tab".to_string()),
+        Key::Backspace => Some("backspace".to_string()),
+        Key::Esc => Some("esc".to_string()),
+        Key::Up => Some("up".to_string()),
+        Key::Down => Some("down".to_string()),
+        Key::Left => Some("left".to_string()),
+        Key::Right => Some("right".to_string()),
+        Key::Home => Some("home".to_string()),
+        Key::End => Some("end".to_string()),
+        Key::PageUp => Some("page_up".to_string()),
+        Key::PageDown => Some("page_down".to_string()),
+        Key::Delete => Some("delete".to_string()),
+        Key::Insert => Some("insert".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use super::{DEFAULT_KEY_MAP, KeyMap, key_name, parse_key};
+    use rustbox::keyboard::Key;
+    use scribe::Buffer;
+    use scribe::buffer::Position;
+
+    #[test]
+    fn default_key_map_binds_keys_in_every_mode() {
+        let key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+
+        assert!(key_map.command_for("normal", &Key::Char('q')).is_some());
+        assert!(key_map.command_for("insert", &Key::Esc).is_some());
+        assert!(key_map.command_for("open", &Key::Ctrl('j')).is_some());
+        assert!(key_map.command_for("normal", &Key::Char(' ')).is_some());
+        assert!(key_map.command_for("normal", &Key::Char('0')).is_some());
+    }
+
+    #[test]
+    fn command_for_returns_none_for_unbound_keys() {
+        let key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+
+        assert!(key_map.command_for("insert", &Key::Char('q')).is_none());
+        assert!(key_map.command_for("nonexistent", &Key::Char('q')).is_none());
+    }
+
+    #[test]
+    fn merge_replaces_existing_bindings() {
+        let mut key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+        key_map.merge("normal:\n  \"j\": cursor::move_up").unwrap();
+
+        // Set up an application with the cursor on the second line.
+        let mut app = ::models::application::new();
+        let mut buffer = Buffer::new();
+        buffer.insert("amp\neditor");
+        buffer.cursor.move_to(Position {
+            line: 1,
+            offset: 0,
+        });
+        app.workspace.add_buffer(buffer);
+
+        // Run the rebound command; it should move up, rather than down.
+        let command = key_map.command_for("normal", &Key::Char('j')).unwrap();
+        command(&mut app);
+        assert_eq!(app.workspace.current_buffer().unwrap().cursor.line, 0);
+    }
+
+    #[test]
+    fn binding_for_prefers_the_shortest_key_name() {
+        let key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+
+        assert_eq!(key_map.binding_for("normal", "cursor::move_down"),
+                   Some("j".to_string()));
+        assert_eq!(key_map.binding_for("normal", "buffer::start_command_group"), None);
+    }
+
+    #[test]
+    fn modes_binding_lists_every_mode_the_command_is_bound_in() {
+        let key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+
+        let modes = key_map.modes_binding("application::switch_to_normal_mode");
+        assert!(modes.contains(&"insert") && modes.contains(&"select"));
+        assert!(!modes.contains(&"normal"));
+        assert!(key_map.modes_binding("buffer::start_command_group").is_empty());
+    }
+
+    #[test]
+    fn merge_rejects_unknown_commands() {
+        let mut key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+
+        assert!(key_map.merge("normal:\n  \"j\": cursor::teleport").is_err());
+    }
+
+    #[test]
+    fn merge_rejects_unknown_keys() {
+        let mut key_map = KeyMap::parse(DEFAULT_KEY_MAP).unwrap();
+
+        assert!(key_map.merge("normal:\n  \"hyper-j\": cursor::move_up").is_err());
+    }
+
+    #[test]
+    fn key_names_survive_a_round_trip() {
+        for name in vec!["j", "0", "space", "ctrl-j", "enter", "page_down", "f5"] {
+            assert_eq!(key_name(&parse_key(name).unwrap()).unwrap(), name);
+        }
+    }
+}
@@ -1 +1,148 @@
+use commands;
+use models::application::{Application, Mode};
+use rustbox::keyboard::Key;
+
+pub mod key_map;
 pub mod modes;
+
+// Published API
+pub use self::key_map::KeyMap;
+
+/// Passes a key to the current mode, running the command it's bound to (if any),
+/// repeated if it was preceded by a count. Typed keys are captured as they're
+/// handled, so that they can be replayed as part of a macro or change.
+pub fn handle(app: &mut Application, key: Key) {
+    // Changes are made up of the keys typed between visits to normal mode.
+    let capturing = !app.macros.replaying();
+    if capturing {
+        if idle(app) {
+            let revision = commands::buffer::revision(app);
+            app.macros.start_change(revision);
+        }
+        app.macros.capture(key);
+    }
+
+    let command = {
+        let key_map = &app.key_map;
+        match app.mode {
+            Mode::Normal => modes::normal::handle(&mut app.count, key_map, key),
+            Mode::Insert(ref mut i) => modes::insert::handle(i, key_map, key),
+            Mode::Jump(ref mut j) => modes::jump::handle(j, key_map, key),
+            Mode::LineJump(ref mut j) => modes::line_jump::handle(j, key_map, key),
+            Mode::SymbolJump(ref mut j) => modes::symbol_jump::handle(j, key_map, key),
+            Mode::Open(ref mut o) => modes::open::handle(o, key_map, key),
+            Mode::BufferList(ref mut b) => modes::buffer_list::handle(b, key_map, key),
+            Mode::ProjectSearch(ref mut p) => modes::project_search::handle(p, key_map, key),
+            Mode::ProjectReplaceInsert(ref mut r) => {
+                modes::project_replace_insert::handle(r, key_map, key)
+            }
+            Mode::ProjectReplace(_) => modes::project_replace::handle(key_map, key),
+            Mode::SaveAs(ref mut s) => modes::save_as::handle(s, key_map, key),
+            Mode::ExternalChange(_) => modes::external_change::handle(key_map, key),
+            Mode::UnsavedChanges(_) => modes::unsaved_changes::handle(key_map, key),
+            Mode::Recovery(_) => modes::recovery::handle(key_map, key),
+            Mode::Register(ref mut r) => modes::register::handle(r, key_map, key),
+            Mode::Macro(ref mut m) => modes::macros::handle(m, key_map, key),
+            Mode::UndoTree(_) => modes::undo_tree::handle(key_map, key),
+            Mode::Select(_) => modes::select::handle(&mut app.count, key_map, key),
+            Mode::SelectLine(_) => modes::select_line::handle(&mut app.count, key_map, key),
+            Mode::SearchInsert(ref mut s) => modes::search_insert::handle(s, key_map, key),
+            Mode::ReplaceInsert(ref mut r) => modes::replace_insert::handle(r, key_map, key),
+            Mode::ReplaceConfirm(_) => modes::replace_confirm::handle(key_map, key),
+            Mode::Command(ref mut c) => modes::command::handle(c, key_map, key),
+            Mode::Exit => None,
+        }
+    };
+
+    if let Some(command) = command {
+        // Start the buffer's undo tree before the command can change it, so
+        // that changes can be undone all the way back to its original state.
+        commands::undo_tree::start(app);
+
+        let buffer_id = current_buffer_id(app);
+        let count = app.count.take().unwrap_or(1);
+        commands::repeat(app, command, count);
+
+        // Remember the buffer we've left, so that we can switch back to it.
+        if current_buffer_id(app) != buffer_id {
+            app.previous_buffer_id = buffer_id;
+        }
+
+        // Add the command's changes to the buffer's undo tree once the
+        // group they're made in is closed (e.g. by leaving insert mode).
+        if !app.command_group_open {
+            commands::undo_tree::record(app);
+        }
+    }
+
+    if capturing && idle(app) {
+        let revision = commands::buffer::revision(app);
+        app.macros.finish_change(revision);
+    }
+}
+
+/// Whether the application is in normal mode, waiting for a new command.
+fn idle(app: &Application) -> bool {
+    match app.mode {
+        Mode::Normal => app.count.is_none(),
+        _ => false,
+    }
+}
+
+fn current_buffer_id(app: &mut Application) -> Option<usize> {
+    app.workspace.current_buffer().and_then(|buffer| buffer.id)
+}
+
+/// Adds digit keys to the specified count, returning whether or not the key
+/// was consumed. Zero is only treated as part of a count if it's not leading,
+/// so that it remains available as a regular binding.
+pub fn accumulate_count(count: &mut Option<usize>, input: &Key) -> bool {
+    let digit = match *input {
+        Key::Char(c) => c.to_digit(10),
+        _ => None,
+    };
+
+    match digit {
+        Some(0) if count.is_none() => false,
+        Some(digit) => {
+            let current = count.unwrap_or(0);
+            *count = Some(current.checked_mul(10)
+                                 .and_then(|value| value.checked_add(digit as usize))
+                                 .unwrap_or(current));
+
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::accumulate_count;
+    use rustbox::keyboard::Key;
+
+    #[test]
+    fn accumulate_count_builds_multi_digit_counts() {
+        let mut count = None;
+
+        assert!(accumulate_count(&mut count, &Key::Char('1')));
+        assert!(accumulate_count(&mut count, &Key::Char('0')));
+        assert_eq!(count, Some(10));
+    }
+
+    #[test]
+    fn accumulate_count_ignores_leading_zeroes() {
+        let mut count = None;
+
+        assert!(!accumulate_count(&mut count, &Key::Char('0')));
+        assert_eq!(count, None);
+    }
+
+    #[test]
+    fn accumulate_count_ignores_non_digit_keys() {
+        let mut count = Some(5);
+
+        assert!(!accumulate_count(&mut count, &Key::Char('j')));
+        assert_eq!(count, Some(5));
+    }
+}
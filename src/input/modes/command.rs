@@ -0,0 +1,38 @@
+use models::application::modes::CommandMode;
+use commands::{Command, command_mode};
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(mode: &mut CommandMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over query input.
+    if let Some(command) = key_map.command_for("command", &input) {
+        return Some(command);
+    }
+
+    match input {
+        Key::Backspace => {
+            // Remove the last token/word from the query.
+            match mode.input.chars().enumerate().filter(|&(_, c)| c == ' ').last() {
+                Some((i, _)) => {
+                    if mode.input.len() == i + 1 {
+                        mode.input.pop();
+                    } else {
+                        mode.input.truncate(i + 1);
+                    }
+                }
+                None => mode.input.clear(),
+            };
+
+            // Re-run the search.
+            Some(command_mode::search)
+        }
+        Key::Char(c) => {
+            // Add a character to the search term.
+            mode.input.push(c);
+
+            // Re-run the search.
+            Some(command_mode::search)
+        }
+        _ => None,
+    }
+}
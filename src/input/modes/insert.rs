@@ -1,22 +1,16 @@
 use models::application::modes::insert::InsertMode;
-use commands::{Command, application, buffer, cursor, view};
+use commands::{Command, buffer};
+use input::KeyMap;
 use rustbox::keyboard::Key;
 
-pub fn handle(mode: &mut InsertMode, input: Key) -> Option<Command> {
+pub fn handle(mode: &mut InsertMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over text input.
+    if let Some(command) = key_map.command_for("insert", &input) {
+        return Some(command);
+    }
+
     match input {
-        Key::Esc       => Some(application::switch_to_normal_mode),
-        Key::Enter     => Some(buffer::insert_newline),
-        Key::Backspace => Some(buffer::backspace),
-        Key::Tab       => Some(buffer::indent_line),
-        Key::Down      => Some(cursor::move_down),
-        Key::Up        => Some(cursor::move_up),
-        Key::Left      => Some(cursor::move_left),
-        Key::Right     => Some(cursor::move_right),
-        Key::Home      => Some(cursor::move_to_start_of_line),
-        Key::End       => Some(cursor::move_to_end_of_line),
-        Key::PageUp    => Some(view::scroll_up),
-        Key::PageDown  => Some(view::scroll_down),
-        Key::Char(c)   => {
+        Key::Char(c) => {
             mode.input = Some(c);
             Some(buffer::insert_char)
         }
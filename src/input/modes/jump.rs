@@ -1,10 +1,15 @@
 use models::application::modes::jump::JumpMode;
-use commands::{Command, application, jump_mode};
+use commands::{Command, jump_mode};
+use input::KeyMap;
 use rustbox::keyboard::Key;
 
-pub fn handle(mode: &mut JumpMode, input: Key) -> Option<Command> {
+pub fn handle(mode: &mut JumpMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over tag input.
+    if let Some(command) = key_map.command_for("jump", &input) {
+        return Some(command);
+    }
+
     match input {
-        Key::Esc => Some(application::switch_to_normal_mode),
         Key::Char(c) => {
             // Add the input to whatever we've received in jump mode so far.
             mode.input.push(c.clone());
@@ -1,11 +1,15 @@
 use models::application::modes::line_jump::LineJumpMode;
-use commands::{Command, application, line_jump};
+use commands::Command;
+use input::KeyMap;
 use rustbox::keyboard::Key;
 
-pub fn handle(mode: &mut LineJumpMode, input: Key) -> Option<Command> {
+pub fn handle(mode: &mut LineJumpMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over text input.
+    if let Some(command) = key_map.command_for("line_jump", &input) {
+        return Some(command);
+    }
+
     match input {
-        Key::Esc => Some(application::switch_to_normal_mode),
-        Key::Enter => Some(line_jump::accept_input),
         Key::Backspace => {
             // Remove a character from the search term.
             mode.input.pop();
@@ -0,0 +1,20 @@
+use models::application::modes::macros::MacroMode;
+use commands::{Command, macros};
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(mode: &mut MacroMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over slot names.
+    if let Some(command) = key_map.command_for("macros", &input) {
+        return Some(command);
+    }
+
+    match input {
+        Key::Char(c) => {
+            mode.name = Some(c);
+
+            Some(macros::select)
+        }
+        _ => None,
+    }
+}
@@ -1,9 +1,23 @@
 pub mod normal;
+pub mod buffer_list;
+pub mod command;
+pub mod external_change;
 pub mod insert;
 pub mod jump;
 pub mod line_jump;
+pub mod macros;
 pub mod symbol_jump;
 pub mod open;
+pub mod project_replace;
+pub mod project_replace_insert;
+pub mod project_search;
+pub mod recovery;
+pub mod register;
+pub mod replace_confirm;
+pub mod replace_insert;
+pub mod save_as;
 pub mod select;
 pub mod select_line;
 pub mod search_insert;
+pub mod undo_tree;
+pub mod unsaved_changes;
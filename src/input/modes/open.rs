@@ -1,8 +1,14 @@
 use models::application::modes::OpenMode;
-use commands::{Command, application, open_mode};
+use commands::{Command, open_mode};
+use input::KeyMap;
 use rustbox::keyboard::Key;
 
-pub fn handle(mode: &mut OpenMode, input: Key) -> Option<Command> {
+pub fn handle(mode: &mut OpenMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over query input.
+    if let Some(command) = key_map.command_for("open", &input) {
+        return Some(command);
+    }
+
     match input {
         Key::Backspace => {
             // Remove the last token/word from the query.
@@ -27,10 +33,6 @@ pub fn handle(mode: &mut OpenMode, input: Key) -> Option<Command> {
             // Re-run the search.
             Some(open_mode::search)
         }
-        Key::Down | Key::Ctrl('j') => Some(open_mode::select_next_path),
-        Key::Up | Key::Ctrl('k') => Some(open_mode::select_previous_path),
-        Key::Enter => Some(open_mode::open),
-        Key::Esc => Some(application::switch_to_normal_mode),
         _ => None,
     }
 }
@@ -0,0 +1,7 @@
+use commands::Command;
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(key_map: &KeyMap, input: Key) -> Option<Command> {
+    key_map.command_for("project_replace", &input)
+}
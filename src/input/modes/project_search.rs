@@ -0,0 +1,33 @@
+use models::application::modes::ProjectSearchMode;
+use commands::Command;
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(mode: &mut ProjectSearchMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over query input.
+    if let Some(command) = key_map.command_for("project_search", &input) {
+        return Some(command);
+    }
+
+    match input {
+        Key::Backspace => {
+            // Remove a character from the query, discarding
+            // results, which no longer apply to it.
+            mode.input.pop();
+            mode.results = None;
+            mode.error = None;
+
+            None
+        }
+        Key::Char(c) => {
+            // Add a character to the query, discarding
+            // results, which no longer apply to it.
+            mode.input.push(c);
+            mode.results = None;
+            mode.error = None;
+
+            None
+        }
+        _ => None,
+    }
+}
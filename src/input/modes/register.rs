@@ -0,0 +1,20 @@
+use models::application::modes::register::RegisterMode;
+use commands::{Command, register};
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(mode: &mut RegisterMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over register names.
+    if let Some(command) = key_map.command_for("register", &input) {
+        return Some(command);
+    }
+
+    match input {
+        Key::Char(c) => {
+            mode.name = Some(c);
+
+            Some(register::select)
+        }
+        _ => None,
+    }
+}
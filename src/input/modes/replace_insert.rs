@@ -0,0 +1,29 @@
+use models::application::modes::replace_insert::ReplaceInsertMode;
+use commands::Command;
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(mode: &mut ReplaceInsertMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over text input.
+    if let Some(command) = key_map.command_for("replace_insert", &input) {
+        return Some(command);
+    }
+
+    match input {
+        Key::Backspace => {
+            // Remove a character from the query or replacement.
+            mode.input().pop();
+            mode.error = None;
+
+            None
+        }
+        Key::Char(c) => {
+            // Add a character to the query or replacement.
+            mode.input().push(c);
+            mode.error = None;
+
+            None
+        }
+        _ => None,
+    }
+}
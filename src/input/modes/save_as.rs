@@ -0,0 +1,31 @@
+use models::application::modes::save_as::SaveAsMode;
+use commands::Command;
+use input::KeyMap;
+use rustbox::keyboard::Key;
+
+pub fn handle(mode: &mut SaveAsMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over text input.
+    if let Some(command) = key_map.command_for("save_as", &input) {
+        return Some(command);
+    }
+
+    match input {
+        Key::Backspace => {
+            // Remove a character from the path.
+            mode.input.pop();
+            mode.error = None;
+            mode.confirming_overwrite = false;
+
+            None
+        }
+        Key::Char(c) => {
+            // Add a character to the path.
+            mode.input.push(c);
+            mode.error = None;
+            mode.confirming_overwrite = false;
+
+            None
+        }
+        _ => None,
+    }
+}
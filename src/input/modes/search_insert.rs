@@ -1,22 +1,30 @@
 use models::application::modes::search_insert::SearchInsertMode;
-use commands::{Command, application, search};
+use commands::{Command, search};
+use input::KeyMap;
 use rustbox::keyboard::Key;
 
-pub fn handle(mode: &mut SearchInsertMode, input: Key) -> Option<Command> {
+pub fn handle(mode: &mut SearchInsertMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over text input.
+    if let Some(command) = key_map.command_for("search_insert", &input) {
+        return Some(command);
+    }
+
     match input {
-        Key::Esc => Some(application::switch_to_normal_mode),
-        Key::Enter => Some(search::accept_query),
         Key::Backspace => {
             // Remove a character from the search term.
             mode.input.pop();
+            mode.error = None;
 
-            None
+            // Move to the first result for the updated query.
+            Some(search::preview_result)
         }
         Key::Char(c) => {
             // Add a character to the search term.
             mode.input.push(c);
+            mode.error = None;
 
-            None
+            // Move to the first result for the updated query.
+            Some(search::preview_result)
         }
         _ => None,
     }
@@ -1,8 +1,14 @@
 use models::application::modes::SymbolJumpMode;
-use commands::{Command, application, symbol_jump};
+use commands::{Command, symbol_jump};
+use input::KeyMap;
 use rustbox::keyboard::Key;
 
-pub fn handle(mode: &mut SymbolJumpMode, input: Key) -> Option<Command> {
+pub fn handle(mode: &mut SymbolJumpMode, key_map: &KeyMap, input: Key) -> Option<Command> {
+    // Bound keys take precedence over query input.
+    if let Some(command) = key_map.command_for("symbol_jump", &input) {
+        return Some(command);
+    }
+
     match input {
         Key::Backspace => {
             // Remove the last token/word from the query.
@@ -27,10 +33,6 @@ pub fn handle(mode: &mut SymbolJumpMode, input: Key) -> Option<Command> {
             // Re-run the search.
             Some(symbol_jump::search)
         }
-        Key::Down | Key::Ctrl('j') => Some(symbol_jump::select_next_symbol),
-        Key::Up | Key::Ctrl('k') => Some(symbol_jump::select_previous_symbol),
-        Key::Enter => Some(symbol_jump::jump_to_selected_symbol),
-        Key::Esc => Some(application::switch_to_normal_mode),
         _ => None,
     }
 }
@@ -14,17 +14,49 @@ mod input;
 mod commands;
 mod presenters;
 
+use std::env;
 use models::application::Mode;
+use models::application::modes::macros::MacroAction;
 use view::terminal::Event;
 
 fn main() {
     let mut application = models::application::new();
 
+    // Pick up where we left off, unless we've been asked to open specific files.
+    if env::args().len() == 1 {
+        commands::session::restore(&mut application);
+    }
+
     loop {
+        // Look for unsaved work left behind by a crash, and changes made to the
+        // current buffer's file outside of the application, unless we're in
+        // the middle of something else.
+        if let Mode::Normal = application.mode {
+            commands::swap::check(&mut application);
+        }
+        if let Mode::Normal = application.mode {
+            commands::external_change::check(&mut application);
+        }
+
+        // Draw the line length guide where the current buffer's file type wants it.
+        application.view.line_length_guide = {
+            let path = application.workspace
+                                  .current_buffer()
+                                  .and_then(|buffer| buffer.path.clone());
+            application.preferences.line_length_guide(path.as_ref())
+        };
+
+        // Additional cursors are drawn alongside the buffer's own.
+        let cursors = {
+            let revision = commands::buffer::revision(&mut application);
+            application.cursors.positions(revision).to_vec()
+        };
+
         // Present the application state to the view.
         match application.mode {
             Mode::Insert(_) => {
                 presenters::modes::insert::display(application.workspace.current_buffer(),
+                                                   &cursors,
                                                    &mut application.view)
             }
             Mode::Open(ref mode) => {
@@ -32,11 +64,76 @@ fn main() {
                                                  mode,
                                                  &mut application.view)
             }
+            Mode::BufferList(ref mode) => {
+                presenters::modes::buffer_list::display(application.workspace.current_buffer(),
+                                                        mode,
+                                                        &mut application.view)
+            }
+            Mode::ProjectSearch(ref mode) => {
+                presenters::modes::project_search::display(application.workspace.current_buffer(),
+                                                           mode,
+                                                           &mut application.view)
+            }
             Mode::SearchInsert(ref mode) => {
                 presenters::modes::search_insert::display(application.workspace.current_buffer(),
                                                           mode,
                                                           &mut application.view)
             }
+            Mode::ReplaceInsert(ref mode) => {
+                presenters::modes::replace_insert::display(application.workspace.current_buffer(),
+                                                           mode,
+                                                           "Replace",
+                                                           &mut application.view)
+            }
+            Mode::ProjectReplaceInsert(ref mode) => {
+                presenters::modes::replace_insert::display(application.workspace.current_buffer(),
+                                                           mode,
+                                                           "Replace in project",
+                                                           &mut application.view)
+            }
+            Mode::ProjectReplace(ref mode) => {
+                presenters::modes::project_replace::display(mode, &mut application.view)
+            }
+            Mode::SaveAs(ref mode) => {
+                presenters::modes::save_as::display(application.workspace.current_buffer(),
+                                                    mode,
+                                                    &mut application.view)
+            }
+            Mode::ExternalChange(ref mode) => {
+                presenters::modes::external_change::display(application.workspace.current_buffer(),
+                                                            mode,
+                                                            &mut application.view)
+            }
+            Mode::UnsavedChanges(ref mode) => {
+                presenters::modes::unsaved_changes::display(mode, &mut application.view)
+            }
+            Mode::Recovery(ref mode) => {
+                presenters::modes::recovery::display(application.workspace.current_buffer(),
+                                                     mode,
+                                                     &mut application.view)
+            }
+            Mode::Register(_) => {
+                presenters::modes::register::display(application.workspace.current_buffer(),
+                                                     "Register",
+                                                     &mut application.view)
+            }
+            Mode::Macro(ref mode) => {
+                let prompt = match mode.action {
+                    MacroAction::Record => "Record macro",
+                    MacroAction::Play => "Play macro",
+                };
+                presenters::modes::register::display(application.workspace.current_buffer(),
+                                                     prompt,
+                                                     &mut application.view)
+            }
+            Mode::UndoTree(ref mode) => {
+                presenters::modes::undo_tree::display(mode, &mut application.view)
+            }
+            Mode::ReplaceConfirm(ref mode) => {
+                presenters::modes::replace_confirm::display(application.workspace.current_buffer(),
+                                                            mode,
+                                                            &mut application.view)
+            }
             Mode::Jump(ref mut mode) => {
                 presenters::modes::jump::display(application.workspace.current_buffer(),
                                                  mode,
@@ -55,6 +152,7 @@ fn main() {
             Mode::Select(ref mode) => {
                 presenters::modes::select::display(application.workspace.current_buffer(),
                                                    mode,
+                                                   &cursors,
                                                    &mut application.view)
             }
             Mode::SelectLine(ref mode) => {
@@ -62,35 +160,42 @@ fn main() {
                                                         mode,
                                                         &mut application.view)
             }
+            Mode::Command(ref mode) => {
+                presenters::modes::command::display(application.workspace.current_buffer(),
+                                                    mode,
+                                                    &mut application.view)
+            }
             Mode::Normal => {
                 presenters::modes::normal::display(application.workspace.current_buffer(),
+                                                   &cursors,
                                                    &mut application.view,
-                                                   &application.repository)
+                                                   &application.repository,
+                                                   &application.search_query,
+                                                   application.search_regex)
             }
             Mode::Exit => ()
         }
 
+        // Messages take over the status line until the next keypress.
+        presenters::messages::display(&application.messages, &mut application.view);
+
+        // The frame's been presented; pass clipboard escape sequences along.
+        if let Some(output) = application.clipboard.take_terminal_output() {
+            application.view.write_raw(&output);
+        }
+
         // Listen for and respond to user input.
         match application.view.listen() {
             Event::KeyEvent(Some(key)) => {
-                // Pass the input to the current mode.
-                let command = match application.mode {
-                    Mode::Normal => input::modes::normal::handle(key),
-                    Mode::Insert(ref mut i) => input::modes::insert::handle(i, key),
-                    Mode::Jump(ref mut j) => input::modes::jump::handle(j, key),
-                    Mode::LineJump(ref mut j) => input::modes::line_jump::handle(j, key),
-                    Mode::SymbolJump(ref mut j) => input::modes::symbol_jump::handle(j, key),
-                    Mode::Open(ref mut o) => input::modes::open::handle(o, key),
-                    Mode::Select(_) => input::modes::select::handle(key),
-                    Mode::SelectLine(_) => input::modes::select_line::handle(key),
-                    Mode::SearchInsert(ref mut s) => input::modes::search_insert::handle(s, key),
-                    Mode::Exit => break,
-                };
+                // The messages have been seen; discard them.
+                application.messages.clear();
+
+                // Pass the input to the current mode, running the resulting command.
+                input::handle(&mut application, key);
 
-                // If the current mode returned a command, run it.
-                match command {
-                    Some(c) => c(&mut application),
-                    None => (),
+                // Keep copies of unsaved work, in case of a crash.
+                if application.swap_files.tick() {
+                    commands::swap::write(&mut application);
                 }
 
                 // Check if the command resulted in an exit, before
@@ -103,4 +208,10 @@ fn main() {
             _ => {}
         }
     }
+
+    // Any unsaved work has been saved or discarded by now.
+    application.swap_files.remove_all();
+
+    // There's no way to report errors once we've exited; saving the session is best-effort.
+    commands::session::save(&mut application);
 }
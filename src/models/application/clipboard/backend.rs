@@ -0,0 +1,277 @@
+extern crate clipboard;
+
+use std::env;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use self::clipboard::ClipboardContext;
+
+/// The names of the backends that can be chosen in the preferences.
+pub const BACKEND_NAMES: [&'static str; 5] = ["auto", "system", "command", "osc52", "none"];
+
+/// Somewhere outside of the application that copied content is shared with
+/// (e.g. the system clipboard), and that pasted content can be read from.
+pub trait ClipboardBackend {
+    /// The backend's content, if it has any and it can be read.
+    fn get_contents(&mut self) -> Option<String>;
+
+    fn set_contents(&mut self, content: &str) -> Result<(), String>;
+
+    /// Escape sequences the backend needs written to the terminal, taken
+    /// once the screen has been drawn, so they don't interleave with it.
+    fn take_terminal_output(&mut self) -> Option<String> {
+        None
+    }
+}
+
+/// Builds the backend with the specified name. When automatically picking one,
+/// the system clipboard is preferred, followed by clipboard commands, and then
+/// OSC 52. Returns None if the backend isn't available, or for "none".
+pub fn from_name(name: &str) -> Option<Box<ClipboardBackend>> {
+    match name {
+        "system" => SystemBackend::new().map(|backend| boxed(backend)),
+        "command" => CommandBackend::detect().map(|backend| boxed(backend)),
+        "osc52" => Some(boxed(Osc52Backend::new())),
+        "auto" => {
+            from_name("system")
+                .or_else(|| from_name("command"))
+                .or_else(|| from_name("osc52"))
+        }
+        _ => None,
+    }
+}
+
+fn boxed<T: ClipboardBackend + 'static>(backend: T) -> Box<ClipboardBackend> {
+    Box::new(backend)
+}
+
+/// The operating system's clipboard, accessed directly. This is usually
+/// unavailable over SSH and in containers, where there's no display server.
+pub struct SystemBackend {
+    context: ClipboardContext,
+}
+
+impl SystemBackend {
+    pub fn new() -> Option<SystemBackend> {
+        ClipboardContext::new().ok().map(|context| SystemBackend { context: context })
+    }
+}
+
+impl ClipboardBackend for SystemBackend {
+    fn get_contents(&mut self) -> Option<String> {
+        self.context.get_contents().ok()
+    }
+
+    fn set_contents(&mut self, content: &str) -> Result<(), String> {
+        self.context.set_contents(content.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+/// Shares content with the clipboard using external commands
+/// (e.g. xclip or wl-copy), piping content into and out of them.
+pub struct CommandBackend {
+    copy_command: Vec<&'static str>,
+    paste_command: Vec<&'static str>,
+}
+
+impl CommandBackend {
+    /// Looks for clipboard commands suited to the environment.
+    pub fn detect() -> Option<CommandBackend> {
+        let candidates: Vec<(bool, Vec<&'static str>, Vec<&'static str>)> = vec![
+            (cfg!(target_os = "macos"), vec!["pbcopy"], vec!["pbpaste"]),
+            (env::var_os("WAYLAND_DISPLAY").is_some(),
+             vec!["wl-copy"],
+             vec!["wl-paste", "--no-newline"]),
+            (env::var_os("DISPLAY").is_some(),
+             vec!["xclip", "-selection", "clipboard", "-in"],
+             vec!["xclip", "-selection", "clipboard", "-out"]),
+            (env::var_os("DISPLAY").is_some(),
+             vec!["xsel", "--clipboard", "--input"],
+             vec!["xsel", "--clipboard", "--output"]),
+        ];
+
+        candidates.into_iter()
+                  .find(|&(applicable, ref copy_command, _)| {
+                      applicable && in_path(copy_command[0])
+                  })
+                  .map(|(_, copy_command, paste_command)| {
+                      CommandBackend {
+                          copy_command: copy_command,
+                          paste_command: paste_command,
+                      }
+                  })
+    }
+}
+
+impl ClipboardBackend for CommandBackend {
+    fn get_contents(&mut self) -> Option<String> {
+        let output = Command::new(self.paste_command[0])
+                         .args(&self.paste_command[1..])
+                         .stdin(Stdio::null())
+                         .stderr(Stdio::null())
+                         .output();
+
+        match output {
+            Ok(ref output) if output.status.success() => {
+                String::from_utf8(output.stdout.clone()).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn set_contents(&mut self, content: &str) -> Result<(), String> {
+        // Some of these commands stay behind to serve the clipboard;
+        // don't leave them holding on to our output streams.
+        let mut child = try!(Command::new(self.copy_command[0])
+                                 .args(&self.copy_command[1..])
+                                 .stdin(Stdio::piped())
+                                 .stdout(Stdio::null())
+                                 .stderr(Stdio::null())
+                                 .spawn()
+                                 .map_err(|e| e.to_string()));
+
+        if let Some(mut stdin) = child.stdin.take() {
+            try!(stdin.write_all(content.as_bytes()).map_err(|e| e.to_string()));
+        }
+
+        match child.wait() {
+            Ok(ref status) if status.success() => Ok(()),
+            Ok(status) => Err(format!("{} exited with {}", self.copy_command[0], status)),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+}
+
+/// Copies content using OSC 52 terminal escape sequences, which terminals that
+/// support them (including over SSH) pass along to the local clipboard. Reading
+/// the clipboard this way is rarely supported, so content can only be copied.
+///
+/// The terminal's output belongs to the view, so sequences are held until
+/// the view takes and writes them out between frames.
+pub struct Osc52Backend {
+    pending: Option<String>,
+}
+
+impl Osc52Backend {
+    pub fn new() -> Osc52Backend {
+        Osc52Backend { pending: None }
+    }
+}
+
+impl ClipboardBackend for Osc52Backend {
+    fn get_contents(&mut self) -> Option<String> {
+        None
+    }
+
+    fn set_contents(&mut self, content: &str) -> Result<(), String> {
+        // Only the latest copy matters; earlier ones would be overwritten.
+        self.pending = Some(osc52_sequence(content, env::var_os("TMUX").is_some()));
+        Ok(())
+    }
+
+    fn take_terminal_output(&mut self) -> Option<String> {
+        self.pending.take()
+    }
+}
+
+/// Keeps content in memory, as if it were a system clipboard.
+/// Used to keep tests from relying on (or changing) the real one.
+pub struct MemoryBackend {
+    content: Option<String>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> MemoryBackend {
+        MemoryBackend { content: None }
+    }
+}
+
+impl ClipboardBackend for MemoryBackend {
+    fn get_contents(&mut self) -> Option<String> {
+        self.content.clone()
+    }
+
+    fn set_contents(&mut self, content: &str) -> Result<(), String> {
+        self.content = Some(content.to_string());
+        Ok(())
+    }
+}
+
+/// Builds the escape sequence that copies the content to the clipboard,
+/// wrapped so that tmux passes it along to the outer terminal, if specified.
+fn osc52_sequence(content: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(content.as_bytes()));
+
+    if tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
+    } else {
+        sequence
+    }
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &'static [u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                                      abcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0],
+                     *chunk.get(1).unwrap_or(&0),
+                     *chunk.get(2).unwrap_or(&0)];
+        let indices = [bytes[0] >> 2,
+                       (bytes[0] & 0b11) << 4 | bytes[1] >> 4,
+                       (bytes[1] & 0b1111) << 2 | bytes[2] >> 6,
+                       bytes[2] & 0b111111];
+
+        // Pad out incomplete chunks.
+        for (position, &index) in indices.iter().enumerate() {
+            if position <= chunk.len() {
+                encoded.push(ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Whether the program can be found in one of the directories in the PATH.
+fn in_path(program: &str) -> bool {
+    match env::var_os("PATH") {
+        Some(paths) => {
+            env::split_paths(&paths).any(|path| Path::new(&path).join(program).is_file())
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64, osc52_sequence, ClipboardBackend, Osc52Backend};
+
+    #[test]
+    fn base64_pads_incomplete_chunks() {
+        assert_eq!(base64(b"amp"), "YW1w");
+        assert_eq!(base64(b"amp!"), "YW1wIQ==");
+        assert_eq!(base64(b"amp!!"), "YW1wISE=");
+        assert_eq!(base64(b""), "");
+    }
+
+    #[test]
+    fn osc52_sequence_is_wrapped_for_tmux() {
+        assert_eq!(osc52_sequence("amp", false), "\x1b]52;c;YW1w\x07");
+        assert_eq!(osc52_sequence("amp", true),
+                   "\x1bPtmux;\x1b\x1b]52;c;YW1w\x07\x1b\\");
+    }
+
+    #[test]
+    fn osc52_backend_holds_the_latest_sequence_until_it_is_taken() {
+        let mut backend = Osc52Backend::new();
+        backend.set_contents("old").unwrap();
+        backend.set_contents("amp").unwrap();
+
+        assert!(backend.take_terminal_output().unwrap().contains("YW1w"));
+        assert!(backend.take_terminal_output().is_none());
+    }
+}
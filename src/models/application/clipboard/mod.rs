@@ -0,0 +1,249 @@
+extern crate scribe;
+
+pub mod backend;
+
+use std::collections::HashMap;
+use scribe::buffer::Position;
+use self::backend::ClipboardBackend;
+
+/// The number of copies kept in the clipboard history.
+pub const HISTORY_SIZE: usize = 10;
+
+/// The register name that refers to the default register.
+pub const DEFAULT_REGISTER: char = '"';
+
+/// Qualifies in-app copy/paste content with structural information, and
+/// synchronizes said content with the OS-level clipboard (preferring it
+/// in scenarios where it differs from the in-app equivalent), through
+/// whichever backend is available. Without one, content stays in-app.
+///
+/// Content can also be kept in named registers, which are used instead of
+/// the default register when selected before copying or pasting. Everything
+/// copied is added to a history, which can be cycled through after pasting.
+pub struct Clipboard {
+    content: ClipboardContent,
+    backend: Option<Box<ClipboardBackend>>,
+    registers: HashMap<char, ClipboardContent>,
+
+    /// The register used by the next copy or paste, if it's not the default.
+    selected_register: Option<char>,
+
+    /// Copied content, newest first.
+    history: Vec<ClipboardContent>,
+}
+
+/// What was pasted most recently, so that it can be replaced with
+/// older content from the clipboard history.
+pub struct LastPaste {
+    /// The buffer's content after pasting; the paste can only be
+    /// replaced if the buffer hasn't been changed since.
+    pub data: String,
+
+    /// Where the cursor was before pasting.
+    pub cursor: Position,
+    pub insert_below: bool,
+
+    /// The clipboard history entry to paste next.
+    pub next_index: usize,
+}
+
+impl Clipboard {
+    pub fn new(backend: Option<Box<ClipboardBackend>>) -> Clipboard {
+        Clipboard {
+            content: ClipboardContent::None,
+            backend: backend,
+            registers: HashMap::new(),
+            selected_register: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Uses the named register for the next copy or paste.
+    pub fn select_register(&mut self, name: char) {
+        self.selected_register = if name == DEFAULT_REGISTER {
+            None
+        } else {
+            Some(name)
+        };
+    }
+
+    /// Whether a named register has been selected for the next copy or paste.
+    pub fn register_selected(&self) -> bool {
+        self.selected_register.is_some()
+    }
+
+    /// Copied content, newest first.
+    pub fn history(&self) -> &[ClipboardContent] {
+        &self.history
+    }
+
+    /// Returns the in-app clipboard content. However, if in-app content
+    /// differs from the system clipboard, the system clipboard content will
+    /// be saved to the in-app clipboard as inline data and returned instead.
+    /// If a named register has been selected, its content is returned instead.
+    pub fn get_content(&mut self) -> &ClipboardContent {
+        if let Some(name) = self.selected_register.take() {
+            return self.registers.entry(name).or_insert(ClipboardContent::None);
+        }
+
+        // Check the system clipboard for newer content.
+        let new_content = match self.backend {
+            Some(ref mut backend) => {
+                match backend.get_contents() {
+                    Some(content) => {
+                        if content.is_empty() {
+                            None
+                        } else {
+                            // There is system clipboard content we can use.
+                            match self.content {
+                                ClipboardContent::Inline(ref app_content) |
+                                ClipboardContent::Block(ref app_content) => {
+                                    // We have in-app clipboard content, too. Prefer
+                                    // the system clipboard content if they differ.
+                                    if content != *app_content {
+                                        Some(ClipboardContent::Inline(content))
+                                    } else {
+                                        None
+                                    }
+                                }
+                                // We have no in-app clipboard content. Use the system's.
+                                _ => Some(ClipboardContent::Inline(content)),
+                            }
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+
+        // Update the in-app clipboard if we've found newer content.
+        if new_content.is_some() {
+            self.content = new_content.unwrap();
+        }
+
+        &self.content
+    }
+
+    // Updates the in-app and system clipboards with the specified content,
+    // or the selected register, if there is one. The in-app clipboard is
+    // updated even if the system clipboard can't be.
+    pub fn set_content(&mut self, content: ClipboardContent) -> Result<(), String> {
+        self.add_to_history(&content);
+
+        if let Some(name) = self.selected_register.take() {
+            self.registers.insert(name, content);
+            return Ok(());
+        }
+
+        // Update the in-app clipboard.
+        self.content = content;
+
+        // Update the system clipboard.
+        match self.content {
+            ClipboardContent::Inline(ref app_content) |
+            ClipboardContent::Block(ref app_content) => {
+                match self.backend {
+                    Some(ref mut backend) => backend.set_contents(app_content),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Escape sequences the backend needs written to the terminal, if any.
+    pub fn take_terminal_output(&mut self) -> Option<String> {
+        match self.backend {
+            Some(ref mut backend) => backend.take_terminal_output(),
+            None => None,
+        }
+    }
+
+    /// Updates the in-app clipboard, leaving the system clipboard as it is,
+    /// e.g. to restore content from a previous session. Content on the system
+    /// clipboard still takes precedence, if it differs.
+    pub fn restore_content(&mut self, content: ClipboardContent) {
+        self.content = content;
+    }
+
+    fn add_to_history(&mut self, content: &ClipboardContent) {
+        if *content == ClipboardContent::None || self.history.first() == Some(content) {
+            return;
+        }
+
+        self.history.insert(0, content.clone());
+        self.history.truncate(HISTORY_SIZE);
+    }
+}
+
+/// In-app content can be captured in both regular and full-line selection
+/// modes. This type describes the structure of said content, based on the
+/// context in which it was captured. When OS-level clipboard contents are
+/// used, they are always represented as inline, as we cannot infer block
+/// style without the copy context.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClipboardContent {
+    Inline(String),
+    Block(String),
+    None,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Clipboard, ClipboardContent, HISTORY_SIZE};
+    use super::backend::{ClipboardBackend, MemoryBackend};
+
+    fn clipboard() -> Clipboard {
+        Clipboard::new(Some(Box::new(MemoryBackend::new())))
+    }
+
+    #[test]
+    fn named_registers_are_used_for_a_single_copy_or_paste() {
+        let mut clipboard = clipboard();
+        clipboard.select_register('a');
+        clipboard.set_content(ClipboardContent::Inline("amp".to_string())).unwrap();
+        assert!(!clipboard.register_selected());
+
+        clipboard.select_register('a');
+        assert_eq!(*clipboard.get_content(),
+                   ClipboardContent::Inline("amp".to_string()));
+        clipboard.select_register('b');
+        assert_eq!(*clipboard.get_content(), ClipboardContent::None);
+    }
+
+    #[test]
+    fn history_keeps_recent_copies_newest_first() {
+        let mut clipboard = clipboard();
+        for index in 0..HISTORY_SIZE + 1 {
+            clipboard.set_content(ClipboardContent::Inline(index.to_string())).unwrap();
+        }
+        clipboard.set_content(ClipboardContent::Inline(HISTORY_SIZE.to_string())).unwrap();
+
+        assert_eq!(clipboard.history().len(), HISTORY_SIZE);
+        assert_eq!(clipboard.history()[0],
+                   ClipboardContent::Inline(HISTORY_SIZE.to_string()));
+        assert_eq!(clipboard.history()[HISTORY_SIZE - 1],
+                   ClipboardContent::Inline("1".to_string()));
+    }
+
+    #[test]
+    fn get_content_prefers_differing_backend_content() {
+        let mut backend = MemoryBackend::new();
+        backend.set_contents("system").unwrap();
+        let mut clipboard = Clipboard::new(Some(Box::new(backend)));
+        clipboard.restore_content(ClipboardContent::Block("amp\n".to_string()));
+
+        assert_eq!(*clipboard.get_content(),
+                   ClipboardContent::Inline("system".to_string()));
+    }
+
+    #[test]
+    fn content_is_kept_in_app_without_a_backend() {
+        let mut clipboard = Clipboard::new(None);
+        clipboard.set_content(ClipboardContent::Block("amp\n".to_string())).unwrap();
+
+        assert_eq!(*clipboard.get_content(),
+                   ClipboardContent::Block("amp\n".to_string()));
+    }
+}
@@ -0,0 +1,157 @@
+extern crate scribe;
+
+use scribe::buffer::Position;
+
+/// Cursors placed in a buffer in addition to its own, at which edits are
+/// repeated. They belong to a single revision of a single buffer, and are
+/// ignored in any other, so that changes made without them (e.g. undoing)
+/// can't leave them pointing at the wrong content.
+pub struct Cursors {
+    revision: Option<(usize, usize)>,
+    positions: Vec<Position>,
+}
+
+/// The extent of an edit: the end of the content it replaced, and the
+/// end of the content that replaced it. Content beyond it is left as-is.
+pub struct Edit {
+    pub old_end: Position,
+    pub new_end: Position,
+}
+
+impl Cursors {
+    pub fn new() -> Cursors {
+        Cursors {
+            revision: None,
+            positions: Vec::new(),
+        }
+    }
+
+    /// The additional cursors in the buffer with the specified
+    /// ID and revision, in the order they were added.
+    pub fn positions(&self, revision: Option<(usize, usize)>) -> &[Position] {
+        if revision.is_some() && revision == self.revision {
+            &self.positions
+        } else {
+            &[]
+        }
+    }
+
+    /// Adds a cursor to the buffer with the specified ID and revision,
+    /// discarding those in any other buffer or revision, returning
+    /// whether there wasn't already one at the same position.
+    pub fn add(&mut self, revision: Option<(usize, usize)>, position: Position) -> bool {
+        if revision != self.revision {
+            self.set(revision, Vec::new());
+        }
+        if self.positions.contains(&position) {
+            return false;
+        }
+
+        self.positions.push(position);
+        true
+    }
+
+    /// Replaces the cursors, e.g. once edits have moved them.
+    pub fn set(&mut self, revision: Option<(usize, usize)>, positions: Vec<Position>) {
+        self.revision = revision;
+        self.positions = positions;
+    }
+
+    pub fn clear(&mut self) {
+        self.set(None, Vec::new());
+    }
+}
+
+impl Edit {
+    /// An edit inserting the text at the specified position.
+    pub fn insertion(position: Position, text: &str) -> Edit {
+        let mut new_end = position;
+        for character in text.chars() {
+            if character == '\n' {
+                new_end.line += 1;
+                new_end.offset = 0;
+            } else {
+                new_end.offset += 1;
+            }
+        }
+
+        Edit {
+            old_end: position,
+            new_end: new_end,
+        }
+    }
+
+    /// Where a position at or after the edit ends up once it's been made.
+    /// Positions within the replaced content are moved to the end of its
+    /// replacement.
+    pub fn shift(&self, position: Position) -> Position {
+        if (position.line, position.offset) < (self.old_end.line, self.old_end.offset) {
+            self.new_end
+        } else if position.line == self.old_end.line {
+            Position {
+                line: self.new_end.line,
+                offset: position.offset - self.old_end.offset + self.new_end.offset,
+            }
+        } else {
+            Position {
+                line: position.line - self.old_end.line + self.new_end.line,
+                offset: position.offset,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use scribe::buffer::Position;
+    use super::{Cursors, Edit};
+
+    #[test]
+    fn cursors_only_belong_to_the_revision_they_were_last_added_to() {
+        let mut cursors = Cursors::new();
+        let position = Position {
+            line: 1,
+            offset: 2,
+        };
+
+        assert!(cursors.add(Some((0, 0)), position));
+        assert!(!cursors.add(Some((0, 0)), position));
+        assert_eq!(cursors.positions(Some((0, 0))).to_vec(), vec![position]);
+        assert!(cursors.positions(Some((0, 1))).is_empty());
+        assert!(cursors.positions(Some((1, 0))).is_empty());
+
+        assert!(cursors.add(Some((1, 0)), position));
+        assert!(cursors.positions(Some((0, 0))).is_empty());
+    }
+
+    #[test]
+    fn shift_moves_positions_past_the_edit_along_with_its_end() {
+        let edit = Edit::insertion(Position {
+                                       line: 0,
+                                       offset: 1,
+                                   },
+                                   "a\nb");
+
+        // Positions on the line the edit ends on keep their distance from it.
+        assert_eq!(edit.shift(Position {
+                       line: 0,
+                       offset: 3,
+                   }),
+                   Position {
+                       line: 1,
+                       offset: 3,
+                   });
+
+        // Positions on later lines only move down.
+        assert_eq!(edit.shift(Position {
+                       line: 2,
+                       offset: 3,
+                   }),
+                   Position {
+                       line: 3,
+                       offset: 3,
+                   });
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A snapshot of a file on disk, used to detect changes made outside of the
+/// application. The modification time and length are a cheap way to rule
+/// changes out; the content hash has the final say, so that files that have
+/// only been touched aren't reported.
+#[derive(Clone, Copy, PartialEq)]
+struct DiskState {
+    metadata: Option<(SystemTime, u64)>,
+    hash: u64,
+}
+
+/// Tracks the on-disk state of files that buffers were last synchronized with.
+pub struct DiskStates {
+    states: HashMap<PathBuf, DiskState>,
+}
+
+impl DiskStates {
+    pub fn new() -> DiskStates {
+        DiskStates { states: HashMap::new() }
+    }
+
+    /// Records the file's current on-disk state as the one its buffer reflects,
+    /// which is expected to happen when it's opened, and whenever it's saved.
+    pub fn record(&mut self, path: &Path) {
+        match read(path) {
+            Some(state) => {
+                self.states.insert(path.to_path_buf(), state);
+            }
+            None => {
+                self.states.remove(path);
+            }
+        }
+    }
+
+    /// Whether the file has changed on disk since its state was recorded.
+    /// Files that haven't been recorded (e.g. they hadn't been saved yet)
+    /// and files that can no longer be read never have.
+    pub fn has_changed(&mut self, path: &Path) -> bool {
+        let recorded_state = match self.states.get(path) {
+            Some(state) => *state,
+            None => return false,
+        };
+
+        // Skip reading the file if it hasn't been modified.
+        if recorded_state.metadata.is_some() && metadata(path) == recorded_state.metadata {
+            return false;
+        }
+
+        match read(path) {
+            Some(state) => {
+                if state.hash == recorded_state.hash {
+                    // The file's been touched but its content hasn't changed;
+                    // record it to avoid reading it again the next time around.
+                    self.states.insert(path.to_path_buf(), state);
+                    false
+                } else {
+                    true
+                }
+            }
+            None => false,
+        }
+    }
+}
+
+/// Reads the file's current state, if possible.
+fn read(path: &Path) -> Option<DiskState> {
+    let mut data = String::new();
+    match File::open(path).and_then(|mut file| file.read_to_string(&mut data)) {
+        Ok(_) => {
+            let mut hasher = DefaultHasher::new();
+            data.hash(&mut hasher);
+
+            Some(DiskState {
+                metadata: metadata(path),
+                hash: hasher.finish(),
+            })
+        }
+        Err(_) => None,
+    }
+}
+
+/// The file's modification time and length.
+fn metadata(path: &Path) -> Option<(SystemTime, u64)> {
+    fs::metadata(path).and_then(|metadata| {
+        metadata.modified().map(|modified| (modified, metadata.len()))
+    }).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use super::DiskStates;
+
+    #[test]
+    fn has_changed_compares_file_content_with_the_recorded_state() {
+        let path = env::temp_dir().join("amp_disk_states_test.txt");
+        File::create(&path).unwrap().write_all(b"amp").unwrap();
+
+        // Files aren't compared until their state has been recorded.
+        let mut states = DiskStates::new();
+        assert!(!states.has_changed(&path));
+        File::create(&path).unwrap().write_all(b"text").unwrap();
+        assert!(!states.has_changed(&path));
+
+        states.record(&path);
+        assert!(!states.has_changed(&path));
+
+        // Rewriting the same content doesn't count as a change.
+        File::create(&path).unwrap().write_all(b"text").unwrap();
+        assert!(!states.has_changed(&path));
+
+        File::create(&path).unwrap().write_all(b"editor").unwrap();
+        assert!(states.has_changed(&path));
+
+        states.record(&path);
+        assert!(!states.has_changed(&path));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use rustbox::keyboard::Key;
+
+/// Keeps track of the keys typed into the application, so that they can be
+/// replayed. Macros are recorded into named slots, while the keys making up
+/// the most recent change to a buffer are kept so that it can be repeated.
+pub struct Macros {
+    slots: HashMap<char, Vec<Key>>,
+    recording: Option<(char, Vec<Key>)>,
+
+    /// What's being replayed (a named macro, or the last change, for None),
+    /// innermost last. Replayed keys aren't captured again.
+    replaying: Vec<Option<char>>,
+
+    /// The keys typed since the application was last idle in normal mode,
+    /// along with the current buffer's ID and revision at the time.
+    pending_change: Option<(Vec<Key>, Option<(usize, usize)>)>,
+    last_change: Vec<Key>,
+}
+
+impl Macros {
+    pub fn new() -> Macros {
+        Macros {
+            slots: HashMap::new(),
+            recording: None,
+            replaying: Vec::new(),
+            pending_change: None,
+            last_change: Vec::new(),
+        }
+    }
+
+    /// The keys recorded into the named slot, if there are any.
+    pub fn get(&self, name: char) -> Option<Vec<Key>> {
+        self.slots.get(&name).map(|keys| keys.clone())
+    }
+
+    /// The keys that made up the most recent change.
+    pub fn last_change(&self) -> Vec<Key> {
+        self.last_change.clone()
+    }
+
+    /// The slot that keys are being recorded into, if any.
+    pub fn recording(&self) -> Option<char> {
+        self.recording.as_ref().map(|&(name, _)| name)
+    }
+
+    pub fn start_recording(&mut self, name: char) {
+        self.recording = Some((name, Vec::new()));
+    }
+
+    /// Saves the recorded keys into their slot, returning its name. The last
+    /// key captured is left out, as it's the one used to stop the recording.
+    pub fn stop_recording(&mut self) -> Option<char> {
+        self.recording.take().map(|(name, mut keys)| {
+            keys.pop();
+            self.slots.insert(name, keys);
+            name
+        })
+    }
+
+    /// Whether keys are being replayed, rather than typed.
+    pub fn replaying(&self) -> bool {
+        !self.replaying.is_empty()
+    }
+
+    /// Marks the named macro (or the last change, for None) as being replayed,
+    /// returning false if it already is, as replaying it would never end.
+    pub fn start_replay(&mut self, name: Option<char>) -> bool {
+        if self.replaying.contains(&name) {
+            return false;
+        }
+
+        self.replaying.push(name);
+        true
+    }
+
+    pub fn end_replay(&mut self) {
+        self.replaying.pop();
+    }
+
+    /// Adds a typed key to the macro being recorded and the pending change.
+    pub fn capture(&mut self, key: Key) {
+        if let Some((_, ref mut keys)) = self.recording {
+            keys.push(key);
+        }
+        if let Some((ref mut keys, _)) = self.pending_change {
+            keys.push(key);
+        }
+    }
+
+    /// Starts collecting the keys for a potential change to the buffer with the
+    /// specified ID and revision, discarding any keys collected so far.
+    pub fn start_change(&mut self, revision: Option<(usize, usize)>) {
+        self.pending_change = Some((Vec::new(), revision));
+    }
+
+    /// Keeps the keys collected since the change was started as the last
+    /// change, if the buffer (or its revision) has changed since.
+    pub fn finish_change(&mut self, revision: Option<(usize, usize)>) {
+        if let Some((keys, original_revision)) = self.pending_change.take() {
+            if revision != original_revision && !keys.is_empty() {
+                self.last_change = keys;
+            }
+        }
+    }
+
+    /// Forgets the keys collected for the pending change, so that commands
+    /// that shouldn't be repeated (e.g. undo) aren't kept as the last change.
+    pub fn discard_change(&mut self) {
+        self.pending_change = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustbox::keyboard::Key;
+    use super::Macros;
+
+    #[test]
+    fn stop_recording_leaves_out_the_key_that_stopped_it() {
+        let mut macros = Macros::new();
+        macros.start_recording('a');
+        macros.capture(Key::Char('j'));
+        macros.capture(Key::Char('Z'));
+
+        assert_eq!(macros.recording(), Some('a'));
+        assert_eq!(macros.stop_recording(), Some('a'));
+        assert_eq!(macros.recording(), None);
+        assert_eq!(macros.get('a'), Some(vec![Key::Char('j')]));
+    }
+
+    #[test]
+    fn finish_change_only_keeps_keys_that_changed_the_buffer() {
+        let mut macros = Macros::new();
+        macros.start_change(Some((0, 0)));
+        macros.capture(Key::Char('x'));
+        macros.finish_change(Some((0, 1)));
+
+        macros.start_change(Some((0, 1)));
+        macros.capture(Key::Char('j'));
+        macros.finish_change(Some((0, 1)));
+
+        assert_eq!(macros.last_change(), vec![Key::Char('x')]);
+    }
+
+    #[test]
+    fn start_replay_rejects_nested_replays_of_the_same_keys() {
+        let mut macros = Macros::new();
+
+        assert!(macros.start_replay(Some('a')));
+        assert!(macros.start_replay(None));
+        assert!(!macros.start_replay(Some('a')));
+        macros.end_replay();
+        macros.end_replay();
+        assert!(!macros.replaying());
+    }
+}
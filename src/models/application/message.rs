@@ -0,0 +1,37 @@
+/// How a message should be presented, based on its severity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MessageKind {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Feedback from a command, shown in the status line until the next keypress.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub kind: MessageKind,
+    pub content: String,
+}
+
+impl Message {
+    pub fn info<T: Into<String>>(content: T) -> Message {
+        Message {
+            kind: MessageKind::Info,
+            content: content.into(),
+        }
+    }
+
+    pub fn warning<T: Into<String>>(content: T) -> Message {
+        Message {
+            kind: MessageKind::Warning,
+            content: content.into(),
+        }
+    }
+
+    pub fn error<T: Into<String>>(content: T) -> Message {
+        Message {
+            kind: MessageKind::Error,
+            content: content.into(),
+        }
+    }
+}
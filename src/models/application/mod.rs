@@ -3,23 +3,56 @@ extern crate scribe;
 extern crate rustbox;
 
 pub mod modes;
+pub mod session;
+pub mod undo_tree;
 mod clipboard;
+mod cursors;
+mod disk_states;
+mod macros;
+mod message;
+mod preferences;
+mod revisions;
+mod swap_files;
 
 // Published API
-pub use self::clipboard::ClipboardContent;
+pub use self::clipboard::{ClipboardContent, LastPaste};
+pub use self::cursors::{Cursors, Edit};
+pub use self::disk_states::DiskStates;
+pub use self::macros::Macros;
+pub use self::message::{Message, MessageKind};
+pub use self::preferences::Preferences;
+pub use self::revisions::Revisions;
+pub use self::swap_files::SwapFiles;
+pub use self::undo_tree::UndoTree;
 
+use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
+use self::modes::buffer_list::BufferListMode;
+use self::modes::command::CommandMode;
 use self::modes::jump::JumpMode;
 use self::modes::line_jump::LineJumpMode;
+use self::modes::macros::MacroMode;
 use self::modes::symbol_jump::SymbolJumpMode;
 use self::modes::insert::InsertMode;
 use self::modes::open::OpenMode;
+use self::modes::project_replace::ProjectReplaceMode;
+use self::modes::project_search::ProjectSearchMode;
 use self::modes::select::SelectMode;
 use self::modes::select_line::SelectLineMode;
 use self::modes::search_insert::SearchInsertMode;
+use self::modes::replace_confirm::ReplaceConfirmMode;
+use self::modes::replace_insert::ReplaceInsertMode;
+use self::modes::save_as::SaveAsMode;
+use self::modes::external_change::ExternalChangeMode;
+use self::modes::unsaved_changes::UnsavedChangesMode;
+use self::modes::recovery::RecoveryMode;
+use self::modes::register::RegisterMode;
+use self::modes::undo_tree::UndoTreeMode;
 use scribe::{Buffer, Workspace};
 use view::View;
+use helpers::indentation::{self, Indentation};
+use input::KeyMap;
 use self::clipboard::Clipboard;
 use self::git2::Repository;
 
@@ -30,19 +63,83 @@ pub enum Mode {
     LineJump(LineJumpMode),
     SymbolJump(SymbolJumpMode),
     Open(OpenMode),
+    BufferList(BufferListMode),
+    ProjectSearch(ProjectSearchMode),
+    ProjectReplaceInsert(ReplaceInsertMode),
+    ProjectReplace(ProjectReplaceMode),
     Select(SelectMode),
     SelectLine(SelectLineMode),
     SearchInsert(SearchInsertMode),
+    ReplaceInsert(ReplaceInsertMode),
+    ReplaceConfirm(ReplaceConfirmMode),
+    SaveAs(SaveAsMode),
+    ExternalChange(ExternalChangeMode),
+    UnsavedChanges(UnsavedChangesMode),
+    Recovery(RecoveryMode),
+    Register(RegisterMode),
+    Macro(MacroMode),
+    UndoTree(UndoTreeMode),
+    Command(CommandMode),
     Exit,
 }
 
 pub struct Application {
     pub mode: Mode,
     pub workspace: Workspace,
+
+    /// Cursors in the current buffer besides its own, at which edits are repeated.
+    pub cursors: Cursors,
     pub search_query: Option<String>,
+
+    /// Whether the search query is a regular expression.
+    pub search_regex: bool,
     pub view: View,
     pub clipboard: Clipboard,
+
+    /// What was pasted most recently, if the paste can still
+    /// be swapped for older content from the clipboard history.
+    pub last_paste: Option<LastPaste>,
+
+    /// Recorded macros, and the keys that made up the last change.
+    pub macros: Macros,
     pub repository: Option<Repository>,
+    pub key_map: KeyMap,
+    pub preferences: Preferences,
+
+    /// Feedback from commands, shown in the status line
+    /// and discarded when the next key is pressed.
+    pub messages: Vec<Message>,
+
+    /// The on-disk state of files as of when their buffers were first
+    /// checked, saved, or reloaded, used to detect external changes.
+    pub disk_states: DiskStates,
+
+    /// Copies of modified buffers, used to recover unsaved work after a crash.
+    pub swap_files: SwapFiles,
+
+    /// How many times each buffer has been changed.
+    pub revisions: Revisions,
+
+    /// Each buffer's edit history, keyed by buffer ID.
+    pub undo_trees: HashMap<usize, UndoTree>,
+
+    /// The indentation detected in each buffer's content when it was
+    /// opened, keyed by buffer ID; None if there wasn't enough to tell.
+    pub indentations: HashMap<usize, Option<Indentation>>,
+
+    /// The buffer that was current before the current one, if any.
+    pub previous_buffer_id: Option<usize>,
+
+    /// A numeric prefix typed before a command, used to repeat it.
+    pub count: Option<usize>,
+
+    /// Set while a command is being repeated, so that the
+    /// command groups it uses are merged into a single one.
+    pub repeating: bool,
+
+    /// Set while a command group is open (e.g. in insert mode), so that the
+    /// changes made in it are recorded as a single state once it's closed.
+    pub command_group_open: bool,
 }
 
 pub fn new() -> Application {
@@ -52,19 +149,60 @@ pub fn new() -> Application {
         Err(_) => panic!("Could not initialize workspace to the current directory."),
     };
 
-    // Try to open the specified file.
-    // TODO: Handle non-existent files as new empty buffers.
+    // Load the key map and preferences before initializing
+    // the terminal, so that errors are legible.
+    let key_map = match load_key_map() {
+        Ok(key_map) => key_map,
+        Err(e) => panic!("Couldn't load key map: {}", e),
+    };
+
+    let preferences = match load_preferences() {
+        Ok(preferences) => preferences,
+        Err(e) => panic!("Couldn't load preferences: {}", e),
+    };
+
+    // Try to open the specified files, recording their on-disk
+    // state so that changes made outside of amp can be detected,
+    // and starting their undo trees before they can be changed.
+    let workspace_path = workspace.path.clone();
+    let mut disk_states = DiskStates::new();
+    let mut indentations = HashMap::new();
+    let mut undo_trees = HashMap::new();
+    let mut messages = Vec::new();
     for path in env::args().skip(1) {
-        let argument_buffer = match Buffer::from_file(PathBuf::from(path.clone())) {
-            Ok(buf) => buf,
-            Err(_) => panic!("Ran into an error trying to open {}.", path),
+        let argument_path = PathBuf::from(path.clone());
+        let argument_buffer = if argument_path.exists() {
+            disk_states.record(&argument_path);
+            match Buffer::from_file(argument_path) {
+                Ok(buf) => buf,
+                Err(_) => panic!("Ran into an error trying to open {}.", path),
+            }
+        } else {
+            // Use an empty buffer for non-existent files;
+            // they'll be created when the buffer is saved.
+            let mut buf = Buffer::new();
+            buf.path = Some(argument_path);
+            buf
         };
 
         workspace.add_buffer(argument_buffer);
+        if let Some(buffer) = workspace.current_buffer() {
+            if let Some(id) = buffer.id {
+                indentations.insert(id, indentation::detect(&buffer.data()));
+            }
+            if let Err(error) = undo_tree::start(&mut undo_trees,
+                                                 buffer,
+                                                 0,
+                                                 &workspace_path,
+                                                 preferences.persistent_undo()) {
+                messages.push(Message::error(error));
+            }
+        }
     }
 
-    let view = View::new();
-    let clipboard = Clipboard::new();
+    let mut view = View::new();
+    view.theme = preferences.theme();
+    let clipboard = Clipboard::new(clipboard_backend(&preferences));
 
     // Try to initialize a repository in the working directory.
     let repo = match Repository::open(&workspace.path) {
@@ -75,9 +213,74 @@ pub fn new() -> Application {
     Application {
         mode: Mode::Normal,
         workspace: workspace,
+        cursors: Cursors::new(),
         search_query: None,
+        search_regex: false,
         view: view,
         clipboard: clipboard,
+        last_paste: None,
+        macros: Macros::new(),
         repository: repo,
+        key_map: key_map,
+        preferences: preferences,
+        messages: messages,
+        disk_states: disk_states,
+        swap_files: SwapFiles::new(swap_directory()),
+        revisions: Revisions::new(),
+        undo_trees: undo_trees,
+        indentations: indentations,
+        previous_buffer_id: None,
+        count: None,
+        repeating: false,
+        command_group_open: false,
     }
 }
+
+/// Tests use the default key map, so that they don't depend on
+/// bindings from the environment they're run in.
+#[cfg(test)]
+fn load_key_map() -> Result<KeyMap, String> {
+    KeyMap::default()
+}
+
+#[cfg(not(test))]
+fn load_key_map() -> Result<KeyMap, String> {
+    KeyMap::load()
+}
+
+/// Tests use the default preferences, so that they don't depend on
+/// those of the environment they're run in (e.g. its tab width).
+#[cfg(test)]
+fn load_preferences() -> Result<Preferences, String> {
+    Ok(Preferences::default())
+}
+
+#[cfg(not(test))]
+fn load_preferences() -> Result<Preferences, String> {
+    Preferences::load()
+}
+
+/// Tests don't keep swap files unless they set up a directory of their own,
+/// so that they don't write to, or remove files from, the real one.
+#[cfg(test)]
+fn swap_directory() -> Option<PathBuf> {
+    None
+}
+
+#[cfg(not(test))]
+fn swap_directory() -> Option<PathBuf> {
+    ::helpers::directories::cache().map(|dir| dir.join("swap"))
+}
+
+/// Tests use an in-memory clipboard, so that they don't depend on, or
+/// overwrite, the clipboard of the environment they're run in.
+#[cfg(test)]
+fn clipboard_backend(_: &Preferences) -> Option<Box<clipboard::backend::ClipboardBackend>> {
+    Some(Box::new(clipboard::backend::MemoryBackend::new()))
+}
+
+#[cfg(not(test))]
+fn clipboard_backend(preferences: &Preferences)
+                     -> Option<Box<clipboard::backend::ClipboardBackend>> {
+    clipboard::backend::from_name(preferences.clipboard())
+}
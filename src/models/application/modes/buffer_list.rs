@@ -0,0 +1,109 @@
+extern crate fragment;
+extern crate scribe;
+
+use helpers::SelectableSet;
+use scribe::Buffer;
+use std::fmt;
+
+pub const MAX_RESULTS: usize = 10;
+
+/// Lists open buffers, narrowing them down using fragment matching.
+pub struct BufferListMode {
+    pub input: String,
+    pub buffers: Vec<BufferListEntry>,
+    pub results: SelectableSet<BufferListEntry>,
+}
+
+/// An open buffer, identified by its workspace id.
+#[derive(Clone)]
+pub struct BufferListEntry {
+    pub id: usize,
+    pub title: String,
+    pub modified: bool,
+}
+
+impl BufferListEntry {
+    /// Describes the buffer, provided it belongs to a workspace (and has an id).
+    pub fn new(buffer: &Buffer) -> Option<BufferListEntry> {
+        let title = match buffer.path {
+            Some(ref path) => path.to_string_lossy().into_owned(),
+            None => "[untitled]".to_string(),
+        };
+
+        buffer.id.map(|id| {
+            BufferListEntry {
+                id: id,
+                title: title,
+                modified: buffer.modified(),
+            }
+        })
+    }
+}
+
+impl fmt::Display for BufferListEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+impl BufferListMode {
+    pub fn new(mut buffers: Vec<BufferListEntry>) -> BufferListMode {
+        buffers.sort_by(|a, b| a.id.cmp(&b.id));
+
+        BufferListMode {
+            input: String::new(),
+            buffers: buffers,
+            results: SelectableSet::new(Vec::new()),
+        }
+    }
+
+    pub fn selected_buffer_id(&self) -> Option<usize> {
+        self.results.selection().map(|entry| entry.id)
+    }
+
+    pub fn search(&mut self) {
+        let results = if self.input.is_empty() {
+            // List every buffer until there's a query to match.
+            self.buffers.clone()
+        } else {
+            fragment::matching::find(&self.input, &self.buffers, self.buffers.len())
+                .into_iter()
+                .map(|r| r.clone())
+                .collect()
+        };
+
+        self.results = SelectableSet::new(results);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferListEntry, BufferListMode};
+
+    fn buffers() -> Vec<BufferListEntry> {
+        vec![
+            BufferListEntry{ id: 2, title: "src/main.rs".to_string(), modified: false },
+            BufferListEntry{ id: 0, title: "README.md".to_string(), modified: true },
+            BufferListEntry{ id: 1, title: "src/view/mod.rs".to_string(), modified: false },
+        ]
+    }
+
+    #[test]
+    fn search_lists_every_buffer_in_the_order_they_were_opened_without_a_query() {
+        let mut mode = BufferListMode::new(buffers());
+        mode.search();
+
+        assert_eq!(mode.selected_buffer_id(), Some(0));
+        assert_eq!(mode.results.len(), 3);
+    }
+
+    #[test]
+    fn search_matches_buffer_titles_using_fragments() {
+        let mut mode = BufferListMode::new(buffers());
+        mode.input = "view".to_string();
+        mode.search();
+
+        assert_eq!(mode.selected_buffer_id(), Some(1));
+        assert_eq!(mode.results.len(), 1);
+    }
+}
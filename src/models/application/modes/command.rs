@@ -0,0 +1,89 @@
+extern crate fragment;
+
+use helpers::SelectableSet;
+use std::fmt;
+
+pub const MAX_RESULTS: usize = 5;
+
+pub struct CommandMode {
+    pub input: String,
+    pub commands: Vec<DisplayableCommand>,
+    pub results: SelectableSet<DisplayableCommand>,
+}
+
+/// A named command, along with the key (if any) bound to it.
+#[derive(Clone)]
+pub struct DisplayableCommand {
+    pub name: &'static str,
+    pub binding: Option<String>,
+}
+
+impl fmt::Display for DisplayableCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl CommandMode {
+    pub fn new(mut commands: Vec<DisplayableCommand>) -> CommandMode {
+        commands.sort_by(|a, b| a.name.cmp(b.name));
+
+        CommandMode {
+            input: String::new(),
+            commands: commands,
+            results: SelectableSet::new(Vec::new()),
+        }
+    }
+
+    pub fn selected_command(&self) -> Option<&'static str> {
+        self.results.selection().map(|command| command.name)
+    }
+
+    pub fn search(&mut self) {
+        let results = if self.input.is_empty() {
+            // List commands alphabetically until there's a query to match.
+            self.commands.iter().take(MAX_RESULTS).cloned().collect()
+        } else {
+            // Find the commands we're looking for using the query, mapping
+            // the result objects to get at the underlying commands.
+            fragment::matching::find(&self.input, &self.commands, MAX_RESULTS)
+                .into_iter()
+                .map(|r| r.clone())
+                .collect()
+        };
+
+        self.results = SelectableSet::new(results);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandMode, DisplayableCommand};
+
+    fn commands() -> Vec<DisplayableCommand> {
+        vec![
+            DisplayableCommand{ name: "git::add", binding: Some("=".to_string()) },
+            DisplayableCommand{ name: "buffer::save", binding: Some("s".to_string()) },
+            DisplayableCommand{ name: "git::copy_remote_url", binding: None },
+        ]
+    }
+
+    #[test]
+    fn search_lists_commands_alphabetically_without_a_query() {
+        let mut mode = CommandMode::new(commands());
+        mode.search();
+
+        assert_eq!(mode.selected_command(), Some("buffer::save"));
+        assert_eq!(mode.results.len(), 3);
+    }
+
+    #[test]
+    fn search_matches_command_names_using_fragments() {
+        let mut mode = CommandMode::new(commands());
+        mode.input = "git remote".to_string();
+        mode.search();
+
+        assert_eq!(mode.selected_command(), Some("git::copy_remote_url"));
+        assert_eq!(mode.results.len(), 1);
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+use helpers::diff::DiffLine;
+
+/// Asks what to do about a buffer whose file has changed on disk: reload
+/// it, keep the buffer's content, or review the differences between them.
+pub struct ExternalChangeMode {
+    pub path: PathBuf,
+
+    /// Changes that reloading would make to the buffer, when being reviewed.
+    pub diff: Option<Vec<DiffLine>>,
+
+    /// The first diff line shown, once it's been scrolled.
+    pub line_offset: usize,
+}
+
+pub fn new(path: PathBuf) -> ExternalChangeMode {
+    ExternalChangeMode {
+        path: path,
+        diff: None,
+        line_offset: 0,
+    }
+}
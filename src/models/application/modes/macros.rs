@@ -0,0 +1,23 @@
+/// What's done with the macro slot typed in macro mode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MacroAction {
+    Record,
+    Play,
+}
+
+/// Waits for the name of the slot to record a macro into, or play one from.
+pub struct MacroMode {
+    pub name: Option<char>,
+    pub action: MacroAction,
+
+    /// The number of times to play the macro.
+    pub count: usize,
+}
+
+pub fn new(action: MacroAction) -> MacroMode {
+    MacroMode {
+        name: None,
+        action: action,
+        count: 1,
+    }
+}
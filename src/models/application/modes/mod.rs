@@ -1,11 +1,28 @@
+pub use self::buffer_list::BufferListMode;
+pub use self::command::CommandMode;
 pub use self::open::OpenMode;
+pub use self::project_replace::ProjectReplaceMode;
+pub use self::project_search::ProjectSearchMode;
 pub use self::symbol_jump::SymbolJumpMode;
 
+pub mod buffer_list;
+pub mod command;
+pub mod external_change;
 pub mod jump;
+pub mod macros;
 pub mod insert;
 pub mod open;
+pub mod project_replace;
+pub mod project_search;
+pub mod recovery;
+pub mod register;
 pub mod select;
 pub mod select_line;
 pub mod search_insert;
+pub mod replace_confirm;
+pub mod replace_insert;
+pub mod save_as;
 pub mod line_jump;
 pub mod symbol_jump;
+pub mod undo_tree;
+pub mod unsaved_changes;
@@ -0,0 +1,192 @@
+extern crate bloodhound;
+extern crate scribe;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use helpers::SelectableSet;
+use helpers::search;
+use models::application::modes::project_search::{MAX_MATCHES, read_files};
+use scribe::buffer::Range;
+use self::bloodhound::Index;
+
+/// Previews the changes a workspace-wide replacement would
+/// make, allowing them to be excluded before it's applied.
+pub struct ProjectReplaceMode {
+    pub changes: SelectableSet<ProjectReplacement>,
+
+    /// Indices of changes that won't be applied.
+    pub excluded: HashSet<usize>,
+
+    /// Set when the search for changes stopped after finding MAX_MATCHES of
+    /// them, leaving later files out. Files are never only partially covered.
+    pub truncated: bool,
+}
+
+/// A single replacement, along with a preview of the line it changes.
+#[derive(Clone)]
+pub struct ProjectReplacement {
+    pub path: PathBuf,
+    pub range: Range,
+    pub replacement: String,
+    pub preview: String,
+}
+
+impl fmt::Display for ProjectReplacement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{}:{}: {}",
+               self.path.to_string_lossy(),
+               self.range.start().line + 1,
+               self.preview.trim())
+    }
+}
+
+impl ProjectReplaceMode {
+    /// Finds the changes needed to replace the query throughout the workspace.
+    /// The contents of open buffers, keyed by their absolute paths, are used
+    /// in place of what's on disk, since they may have unsaved changes.
+    pub fn new(path: PathBuf,
+               query: &str,
+               replacement: &str,
+               regex: bool,
+               open_buffers: &HashMap<PathBuf, String>)
+               -> ProjectReplaceMode {
+        // Build and populate the index, which
+        // excludes the same paths as open mode.
+        let mut index = Index::new(path.clone());
+        index.populate();
+
+        let mut changes = Vec::new();
+        let truncated = read_files(&index, &path, |indexed_path, disk_data| {
+            let data = match open_buffers.get(&path.join(indexed_path)) {
+                Some(buffer_data) => &buffer_data[..],
+                None => disk_data,
+            };
+            let lines: Vec<&str> = data.split('\n').collect();
+
+            let replacements = search::find_all_replacements(data, query, replacement, regex);
+            for (range, expanded_replacement) in replacements {
+                let preview = preview(lines[range.start().line], &range, &expanded_replacement);
+                changes.push(ProjectReplacement {
+                    path: indexed_path.clone(),
+                    range: range,
+                    replacement: expanded_replacement,
+                    preview: preview,
+                });
+            }
+
+            changes.len() < MAX_MATCHES
+        });
+
+        ProjectReplaceMode {
+            changes: SelectableSet::new(changes),
+            excluded: HashSet::new(),
+            truncated: truncated,
+        }
+    }
+
+    /// Includes the selected change if it's been excluded, and vice versa.
+    pub fn toggle_selected_change(&mut self) {
+        let index = self.changes.selected_index();
+        if !self.excluded.remove(&index) {
+            self.excluded.insert(index);
+        }
+    }
+
+    /// The changes to be applied, grouped by path, in the order they were found.
+    pub fn included_changes(&self) -> Vec<(PathBuf, Vec<ProjectReplacement>)> {
+        let mut grouped_changes: Vec<(PathBuf, Vec<ProjectReplacement>)> = Vec::new();
+
+        for (index, change) in self.changes.iter().enumerate() {
+            if self.excluded.contains(&index) {
+                continue;
+            }
+
+            // Changes are found one file at a time, so
+            // they only ever belong to the latest group.
+            let same_path = match grouped_changes.last() {
+                Some(&(ref path, _)) => *path == change.path,
+                None => false,
+            };
+            if same_path {
+                if let Some(&mut (_, ref mut changes)) = grouped_changes.last_mut() {
+                    changes.push(change.clone());
+                }
+            } else {
+                grouped_changes.push((change.path.clone(), vec![change.clone()]));
+            }
+        }
+
+        grouped_changes
+    }
+}
+
+/// Applies a replacement to the line containing it.
+fn preview(line: &str, range: &Range, replacement: &str) -> String {
+    let mut preview: String = line.chars().take(range.start().offset).collect();
+    preview.push_str(replacement);
+    preview.extend(line.chars().skip(range.end().offset));
+
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use helpers::SelectableSet;
+    use scribe::buffer::{Position, Range};
+    use super::{ProjectReplaceMode, ProjectReplacement, preview};
+
+    fn change(path: &str, line: usize) -> ProjectReplacement {
+        ProjectReplacement {
+            path: PathBuf::from(path),
+            range: Range::new(Position {
+                                  line: line,
+                                  offset: 0,
+                              },
+                              Position {
+                                  line: line,
+                                  offset: 3,
+                              }),
+            replacement: "amp".to_string(),
+            preview: "amp".to_string(),
+        }
+    }
+
+    #[test]
+    fn preview_applies_the_replacement_to_the_line() {
+        let range = Range::new(Position {
+                                   line: 0,
+                                   offset: 4,
+                               },
+                               Position {
+                                   line: 0,
+                                   offset: 5,
+                               });
+        assert_eq!(preview("amp éditor", &range, "e"), "amp editor");
+    }
+
+    #[test]
+    fn included_changes_groups_changes_by_path_and_skips_exclusions() {
+        let mut mode = ProjectReplaceMode {
+            changes: SelectableSet::new(vec![change("a.rs", 0),
+                                             change("a.rs", 1),
+                                             change("b.rs", 0)]),
+            excluded: HashSet::new(),
+            truncated: false,
+        };
+        mode.changes.select_next();
+        mode.toggle_selected_change();
+
+        let changes = mode.included_changes();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].0, PathBuf::from("a.rs"));
+        assert_eq!(changes[0].1.len(), 1);
+        assert_eq!(changes[0].1[0].range.start().line, 0);
+        assert_eq!(changes[1].0, PathBuf::from("b.rs"));
+    }
+}
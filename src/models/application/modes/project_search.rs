@@ -0,0 +1,158 @@
+extern crate bloodhound;
+extern crate scribe;
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use helpers::SelectableSet;
+use helpers::search;
+use scribe::buffer::Position;
+use self::bloodhound::Index;
+
+/// The number of results shown at once; the list scrolls a page at a time.
+pub const MAX_RESULTS: usize = 10;
+
+/// Upper bounds on the work done by a single search, so that huge projects
+/// don't lock up the editor. Searches stop at the end of the file in which
+/// they reach the match limit, so that files are never partially covered.
+const MAX_FILES: usize = 10000;
+pub const MAX_MATCHES: usize = 1000;
+
+pub struct ProjectSearchMode {
+    pub input: String,
+    pub regex: bool,
+
+    /// Set when the input can't be used as a search query.
+    pub error: Option<String>,
+
+    /// Results for the current input, or None if it hasn't been searched for.
+    pub results: Option<SelectableSet<ProjectSearchResult>>,
+
+    /// Set when the search stopped after finding MAX_MATCHES
+    /// matches, before every file had been searched.
+    pub truncated: bool,
+    path: PathBuf,
+    index: Index,
+}
+
+/// A single match, along with the line it was found on.
+#[derive(Clone)]
+pub struct ProjectSearchResult {
+    pub path: PathBuf,
+    pub position: Position,
+    pub line: String,
+}
+
+impl fmt::Display for ProjectSearchResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "{}:{}: {}",
+               self.path.to_string_lossy(),
+               self.position.line + 1,
+               self.line.trim())
+    }
+}
+
+impl ProjectSearchMode {
+    pub fn new(path: PathBuf, regex: bool) -> ProjectSearchMode {
+        // Build and populate the index, which
+        // excludes the same paths as open mode.
+        let mut index = Index::new(path.clone());
+        index.populate();
+
+        ProjectSearchMode {
+            input: String::new(),
+            regex: regex,
+            error: None,
+            results: None,
+            truncated: false,
+            path: path,
+            index: index,
+        }
+    }
+
+    pub fn selected_result(&self) -> Option<&ProjectSearchResult> {
+        match self.results {
+            Some(ref results) => results.selection(),
+            None => None,
+        }
+    }
+
+    /// Searches every indexed file for the input.
+    pub fn search(&mut self) {
+        if self.regex {
+            if let Err(error) = search::compile(&self.input) {
+                self.error = Some(error);
+                return;
+            }
+        }
+        self.error = None;
+
+        let mut results = Vec::new();
+        let truncated = read_files(&self.index, &self.path, |path, data| {
+            let lines: Vec<&str> = data.split('\n').collect();
+            for range in search::find_all(data, &self.input, self.regex) {
+                let position = range.start();
+                results.push(ProjectSearchResult {
+                    path: path.clone(),
+                    position: position,
+                    line: lines[position.line].to_string(),
+                });
+            }
+
+            results.len() < MAX_MATCHES
+        });
+
+        self.results = Some(SelectableSet::new(results));
+        self.truncated = truncated;
+    }
+}
+
+/// Reads each of the index's files, passing its path and contents to the
+/// callback, until it returns false. Files that can't be read as text (e.g.
+/// binary files) are skipped. Indexed paths are relative to the specified one.
+/// Returns whether the callback stopped it before every file had been read.
+pub fn read_files<F>(index: &Index, path: &Path, mut f: F) -> bool
+    where F: FnMut(&PathBuf, &str) -> bool
+{
+    // An empty query matches every indexed path.
+    let mut indexed_paths = index.find("", MAX_FILES).into_iter().peekable();
+    while let Some(indexed_path) = indexed_paths.next() {
+        let mut data = String::new();
+        let read = File::open(path.join(&indexed_path))
+                       .and_then(|mut file| file.read_to_string(&mut data));
+        if read.is_err() {
+            continue;
+        }
+
+        if !f(&indexed_path, &data) {
+            return indexed_paths.peek().is_some();
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use std::path::PathBuf;
+    use scribe::buffer::Position;
+    use super::ProjectSearchResult;
+
+    #[test]
+    fn results_display_their_path_line_number_and_trimmed_line() {
+        let result = ProjectSearchResult {
+            path: PathBuf::from("src/main.rs"),
+            position: Position {
+                line: 2,
+                offset: 4,
+            },
+            line: "    mod input;".to_string(),
+        };
+
+        assert_eq!(result.to_string(), "src/main.rs:3: mod input;");
+    }
+}
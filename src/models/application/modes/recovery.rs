@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+/// Asks whether to recover unsaved work from a swap file left behind
+/// (e.g. by a crash) for the current buffer's file, or discard it.
+pub struct RecoveryMode {
+    pub path: PathBuf,
+    pub swap_path: PathBuf,
+}
+
+pub fn new(path: PathBuf, swap_path: PathBuf) -> RecoveryMode {
+    RecoveryMode {
+        path: path,
+        swap_path: swap_path,
+    }
+}
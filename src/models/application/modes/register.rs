@@ -0,0 +1,16 @@
+use models::application::modes::jump::SelectModeOptions;
+
+/// Waits for a register name to be typed, which the next copy or paste uses.
+/// Like jump mode, it can be entered from the select modes, which are
+/// returned to (with their selections intact) once the name's been typed.
+pub struct RegisterMode {
+    pub name: Option<char>,
+    pub select_mode: SelectModeOptions,
+}
+
+pub fn new() -> RegisterMode {
+    RegisterMode {
+        name: None,
+        select_mode: SelectModeOptions::None,
+    }
+}
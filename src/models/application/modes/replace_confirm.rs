@@ -0,0 +1,24 @@
+extern crate scribe;
+
+use scribe::buffer::Range;
+
+/// Steps through matches of a query, one at a time,
+/// confirming whether or not each should be replaced.
+pub struct ReplaceConfirmMode {
+    pub query: String,
+    pub replacement: String,
+    pub regex: bool,
+
+    /// The match being confirmed, along with its replacement,
+    /// which may differ from the others when using capture groups.
+    pub current_match: Option<(Range, String)>,
+}
+
+pub fn new(query: String, replacement: String, regex: bool) -> ReplaceConfirmMode {
+    ReplaceConfirmMode {
+        query: query,
+        replacement: replacement,
+        regex: regex,
+        current_match: None,
+    }
+}
@@ -0,0 +1,32 @@
+/// Prompts for a search query, followed by its replacement.
+pub struct ReplaceInsertMode {
+    pub query: String,
+    pub replacement: Option<String>,
+
+    /// Whether the query is matched as a regular expression,
+    /// in which case the replacement can refer to its capture groups.
+    pub regex: bool,
+
+    /// Set when the query can't be used to find matches.
+    pub error: Option<String>,
+}
+
+impl ReplaceInsertMode {
+    /// The value currently being entered: the query, until
+    /// it's been accepted, and the replacement thereafter.
+    pub fn input(&mut self) -> &mut String {
+        match self.replacement {
+            Some(ref mut replacement) => replacement,
+            None => &mut self.query,
+        }
+    }
+}
+
+pub fn new(query: String, regex: bool) -> ReplaceInsertMode {
+    ReplaceInsertMode {
+        query: query,
+        replacement: None,
+        regex: regex,
+        error: None,
+    }
+}
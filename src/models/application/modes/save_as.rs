@@ -0,0 +1,113 @@
+use std::fs;
+use std::path::Path;
+
+/// Prompts for a path to save the current buffer to.
+pub struct SaveAsMode {
+    pub input: String,
+
+    /// Set when the buffer couldn't be saved to the input path.
+    pub error: Option<String>,
+
+    /// Set once we've warned that a file already exists at the input
+    /// path, so that accepting it again overwrites the file.
+    pub confirming_overwrite: bool,
+}
+
+impl SaveAsMode {
+    /// Completes the last component of the input using the entries
+    /// of the directory it refers to, relative to the specified base
+    /// path. Completes as far as possible when there are several
+    /// candidates, and appends a separator to directories.
+    pub fn complete(&mut self, base: &Path) {
+        // Split the input into the directory and the partial name within it.
+        let (directory, prefix) = match self.input.rfind('/') {
+            Some(index) => {
+                (self.input[..index + 1].to_string(), self.input[index + 1..].to_string())
+            }
+            None => (String::new(), self.input.clone()),
+        };
+
+        let entries = match fs::read_dir(base.join(&directory)) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        let mut candidates = Vec::new();
+        for entry in entries {
+            if let Ok(entry) = entry {
+                if let Ok(name) = entry.file_name().into_string() {
+                    if name.starts_with(&prefix) {
+                        if entry.path().is_dir() {
+                            candidates.push(format!("{}/", name));
+                        } else {
+                            candidates.push(name);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(completion) = common_prefix(&candidates) {
+            self.input = format!("{}{}", directory, completion);
+        }
+    }
+}
+
+pub fn new(input: String) -> SaveAsMode {
+    SaveAsMode {
+        input: input,
+        error: None,
+        confirming_overwrite: false,
+    }
+}
+
+/// Finds the longest prefix shared by all of the values.
+fn common_prefix(values: &Vec<String>) -> Option<String> {
+    let mut prefix = match values.first() {
+        Some(value) => value.clone(),
+        None => return None,
+    };
+
+    for value in values.iter().skip(1) {
+        prefix = prefix.chars()
+                       .zip(value.chars())
+                       .take_while(|&(a, b)| a == b)
+                       .map(|(a, _)| a)
+                       .collect();
+    }
+
+    Some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use super::{common_prefix, new};
+
+    #[test]
+    fn common_prefix_finds_the_longest_shared_prefix() {
+        let values = vec!["amp_editor".to_string(), "amp_edit".to_string(), "amp_e".to_string()];
+        assert_eq!(common_prefix(&values), Some("amp_e".to_string()));
+        assert_eq!(common_prefix(&Vec::new()), None);
+    }
+
+    #[test]
+    fn complete_extends_the_input_using_directory_entries() {
+        let directory = env::temp_dir().join("amp_save_as_completion_test");
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(directory.join("source")).unwrap();
+        File::create(directory.join("source").join("main.rs")).unwrap();
+
+        // Directories get a trailing separator.
+        let mut mode = new("sou".to_string());
+        mode.complete(&directory);
+        assert_eq!(mode.input, "source/");
+
+        // Completion continues within directories.
+        mode.complete(&directory);
+        assert_eq!(mode.input, "source/main.rs");
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
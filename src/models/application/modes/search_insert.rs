@@ -1,9 +1,35 @@
 extern crate scribe;
 
+use scribe::buffer::{Position, Range};
+
 pub struct SearchInsertMode {
     pub input: String,
+
+    /// Whether the input is matched as a regular expression.
+    pub regex: bool,
+
+    /// Set when the input can't be used as a search query.
+    pub error: Option<String>,
+
+    /// The result the cursor has been moved to while typing.
+    pub current_result: Option<Range>,
+
+    /// Where the cursor and view were before searching,
+    /// so that they can be restored if it's cancelled.
+    pub original_position: Position,
+    pub original_line_offset: usize,
 }
 
-pub fn new() -> SearchInsertMode {
-    SearchInsertMode { input: String::new() }
+pub fn new(regex: bool,
+           original_position: Position,
+           original_line_offset: usize)
+           -> SearchInsertMode {
+    SearchInsertMode {
+        input: String::new(),
+        regex: regex,
+        error: None,
+        current_result: None,
+        original_position: original_position,
+        original_line_offset: original_line_offset,
+    }
 }
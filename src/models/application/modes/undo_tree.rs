@@ -0,0 +1,85 @@
+use helpers::SelectableSet;
+use models::application::undo_tree::UndoTree;
+
+/// Lists the states in the current buffer's undo tree,
+/// newest first, so that any of them can be restored.
+pub struct UndoTreeMode {
+    pub states: SelectableSet<UndoTreeEntry>,
+}
+
+/// Describes a state in an undo tree.
+pub struct UndoTreeEntry {
+    pub index: usize,
+
+    /// When the state was recorded, in seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub parent: Option<usize>,
+
+    /// Whether the state started a new branch (i.e. it was recorded after undoing).
+    pub branch: bool,
+    pub current: bool,
+}
+
+impl UndoTreeMode {
+    pub fn selected_index(&self) -> Option<usize> {
+        self.states.selection().map(|entry| entry.index)
+    }
+}
+
+/// Describes the tree's states, selecting the current one.
+pub fn new(tree: &UndoTree) -> UndoTreeMode {
+    let states = tree.states();
+    let mut entries = Vec::new();
+    for (index, state) in states.iter().enumerate().rev() {
+        // Siblings recorded earlier mean this state was recorded after undoing.
+        let branch = state.parent.is_some() &&
+                     states[..index].iter().any(|earlier| earlier.parent == state.parent);
+
+        entries.push(UndoTreeEntry {
+            index: index,
+            timestamp: state.timestamp,
+            parent: state.parent,
+            branch: branch,
+            current: index == tree.current_index(),
+        });
+    }
+
+    // Start with the current state selected.
+    let position = entries.iter().position(|entry| entry.current).unwrap_or(0);
+    let mut mode = UndoTreeMode { states: SelectableSet::new(entries) };
+    for _ in 0..position {
+        mode.states.select_next();
+    }
+
+    mode
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use models::application::undo_tree::UndoTree;
+    use scribe::buffer::Position;
+
+    #[test]
+    fn new_lists_states_newest_first_and_selects_the_current_one() {
+        let position = Position {
+            line: 0,
+            offset: 0,
+        };
+        let mut tree = UndoTree::new("amp".to_string(), position);
+        tree.record("amp editor".to_string(), position);
+        tree.undo();
+        tree.record("amp text editor".to_string(), position);
+        tree.undo();
+
+        let mode = super::new(&tree);
+        let indices: Vec<usize> = mode.states.iter().map(|entry| entry.index).collect();
+        assert_eq!(indices, vec![2, 1, 0]);
+        assert_eq!(mode.selected_index(), Some(0));
+
+        // Only the state recorded after undoing starts a branch.
+        let branches: Vec<bool> = mode.states.iter().map(|entry| entry.branch).collect();
+        assert_eq!(branches, vec![true, false, false]);
+    }
+}
@@ -0,0 +1,22 @@
+use models::application::modes::buffer_list::BufferListEntry;
+
+/// What to do once buffers with unsaved changes have been dealt with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PendingAction {
+    CloseBuffer,
+    Exit,
+}
+
+/// Asks whether buffers with unsaved changes should be saved or
+/// discarded, before closing them (or the application) for good.
+pub struct UnsavedChangesMode {
+    pub action: PendingAction,
+    pub buffers: Vec<BufferListEntry>,
+}
+
+pub fn new(action: PendingAction, buffers: Vec<BufferListEntry>) -> UnsavedChangesMode {
+    UnsavedChangesMode {
+        action: action,
+        buffers: buffers,
+    }
+}
@@ -0,0 +1,352 @@
+extern crate yaml_rust;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use helpers;
+use helpers::directories;
+use helpers::indentation::Indentation;
+use view::Theme;
+use super::clipboard::backend;
+use self::yaml_rust::{Yaml, YamlLoader};
+
+const PREFERENCES_FILE: &'static str = "config.yml";
+const DEFAULT_TAB_WIDTH: usize = 2;
+const DEFAULT_LINE_LENGTH_GUIDE: usize = 80;
+
+/// Conventional indentation for common languages, used unless
+/// overridden by the user's settings, general or for the file type.
+const LANGUAGE_INDENTATION: &'static [(&'static str, Indentation)] = &[
+    ("c", Indentation::Spaces(4)),
+    ("go", Indentation::Tabs),
+    ("h", Indentation::Spaces(4)),
+    ("java", Indentation::Spaces(4)),
+    ("mk", Indentation::Tabs),
+    ("py", Indentation::Spaces(4)),
+    ("rs", Indentation::Spaces(4)),
+];
+
+/// User-configurable editor settings, loaded from the user's config file.
+/// Buffer settings can be overridden for specific file types, keyed by
+/// their extension, falling back to the general settings, then the
+/// built-in language conventions, and then the built-in defaults:
+///
+///     theme: light
+///     persistent_undo: true
+///     clipboard: osc52
+///     soft_tabs: true
+///     tab_width: 2
+///     line_length_guide: 100
+///     trim_trailing_whitespace: true
+///     ensure_trailing_newline: true
+///     types:
+///       rs:
+///         tab_width: 4
+///       md:
+///         line_length_guide: false
+///         trim_trailing_whitespace: false
+pub struct Preferences {
+    theme: Theme,
+
+    /// Whether buffers' undo trees are saved alongside their files.
+    persistent_undo: bool,
+
+    /// The name of the backend used to share content with the system clipboard.
+    clipboard: String,
+    settings: BufferSettings,
+    type_settings: HashMap<String, BufferSettings>,
+    language_settings: HashMap<String, BufferSettings>,
+}
+
+/// Settings that apply to buffers; unset values fall back to the defaults.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct BufferSettings {
+    /// Whether to indent using spaces rather than tabs.
+    soft_tabs: Option<bool>,
+    tab_width: Option<usize>,
+
+    /// Zero disables the guide.
+    line_length_guide: Option<usize>,
+    trim_trailing_whitespace: Option<bool>,
+    ensure_trailing_newline: Option<bool>,
+}
+
+impl Preferences {
+    /// Loads the user's preferences, using the defaults if there aren't any.
+    pub fn load() -> Result<Preferences, String> {
+        if let Some(path) = directories::config().map(|dir| dir.join(PREFERENCES_FILE)) {
+            if path.exists() {
+                let data = try!(helpers::read_file(&path).map_err(|e| {
+                    format!("Couldn't read {}: {}", path.to_string_lossy(), e)
+                }));
+
+                return Preferences::parse(&data);
+            }
+        }
+
+        Ok(Preferences::default())
+    }
+
+    pub fn default() -> Preferences {
+        Preferences {
+            theme: Theme::Dark,
+            persistent_undo: false,
+            clipboard: "auto".to_string(),
+            settings: BufferSettings::default(),
+            type_settings: HashMap::new(),
+            language_settings: language_settings(),
+        }
+    }
+
+    /// Builds preferences using the settings in the specified YAML data.
+    pub fn parse(data: &str) -> Result<Preferences, String> {
+        let documents = try!(YamlLoader::load_from_str(data).map_err(|e| e.to_string()));
+        let document = match documents.first() {
+            Some(document @ &Yaml::Hash(_)) => document,
+            // An empty config file doesn't change anything.
+            Some(&Yaml::Null) | None => return Ok(Preferences::default()),
+            _ => return Err("Preferences must be structured as a hash.".to_string()),
+        };
+
+        let theme = match document["theme"] {
+            Yaml::String(ref theme) if theme == "dark" => Theme::Dark,
+            Yaml::String(ref theme) if theme == "light" => Theme::Light,
+            Yaml::BadValue => Theme::Dark,
+            _ => return Err("The theme must be either dark or light.".to_string()),
+        };
+
+        let persistent_undo = try!(boolean(document, "persistent_undo")).unwrap_or(false);
+
+        let clipboard = match document["clipboard"] {
+            Yaml::String(ref name) if backend::BACKEND_NAMES.contains(&name.as_str()) => {
+                name.clone()
+            }
+            Yaml::BadValue => "auto".to_string(),
+            _ => {
+                return Err(format!("The clipboard must be one of: {}.",
+                                   backend::BACKEND_NAMES.join(", ")))
+            }
+        };
+
+        let mut type_settings = HashMap::new();
+        match document["types"] {
+            Yaml::Hash(ref types) => {
+                for (extension, settings) in types {
+                    let extension = match extension.as_str() {
+                        Some(extension) => extension.to_string(),
+                        None => return Err("File type extensions must be strings.".to_string()),
+                    };
+                    let settings = try!(BufferSettings::parse(settings).map_err(|e| {
+                        format!("{} (for .{} files)", e, extension)
+                    }));
+                    type_settings.insert(extension, settings);
+                }
+            }
+            Yaml::BadValue => (),
+            _ => return Err("File type settings must be a hash of extensions.".to_string()),
+        }
+
+        Ok(Preferences {
+            theme: theme,
+            persistent_undo: persistent_undo,
+            clipboard: clipboard,
+            settings: try!(BufferSettings::parse(document)),
+            type_settings: type_settings,
+            language_settings: language_settings(),
+        })
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    pub fn persistent_undo(&self) -> bool {
+        self.persistent_undo
+    }
+
+    pub fn clipboard(&self) -> &str {
+        &self.clipboard
+    }
+
+    /// The number of spaces used for each level of indentation.
+    pub fn tab_width(&self, path: Option<&PathBuf>) -> usize {
+        self.setting(path, |settings| settings.tab_width).unwrap_or(DEFAULT_TAB_WIDTH)
+    }
+
+    /// The indentation used for files without any of their own.
+    pub fn indentation(&self, path: Option<&PathBuf>) -> Indentation {
+        if self.setting(path, |settings| settings.soft_tabs).unwrap_or(true) {
+            Indentation::Spaces(self.tab_width(path))
+        } else {
+            Indentation::Tabs
+        }
+    }
+
+    /// The column at which the line length guide is drawn, if it's enabled.
+    pub fn line_length_guide(&self, path: Option<&PathBuf>) -> Option<usize> {
+        match self.setting(path, |settings| settings.line_length_guide) {
+            Some(0) => None,
+            Some(offset) => Some(offset),
+            None => Some(DEFAULT_LINE_LENGTH_GUIDE),
+        }
+    }
+
+    /// Whether trailing whitespace is removed when saving.
+    pub fn trim_trailing_whitespace(&self, path: Option<&PathBuf>) -> bool {
+        self.setting(path, |settings| settings.trim_trailing_whitespace).unwrap_or(true)
+    }
+
+    /// Whether a trailing newline is added (if missing) when saving.
+    pub fn ensure_trailing_newline(&self, path: Option<&PathBuf>) -> bool {
+        self.setting(path, |settings| settings.ensure_trailing_newline).unwrap_or(true)
+    }
+
+    /// Looks up a setting, preferring the user's one for the path's file type,
+    /// then their general one, and then the file type's language convention.
+    fn setting<T, F>(&self, path: Option<&PathBuf>, value: F) -> Option<T>
+        where F: Fn(&BufferSettings) -> Option<T>
+    {
+        let extension = path.and_then(|path| path.extension())
+                            .and_then(|extension| extension.to_str());
+
+        extension.and_then(|extension| self.type_settings.get(extension))
+                 .and_then(|settings| value(settings))
+                 .or_else(|| value(&self.settings))
+                 .or_else(|| {
+                     extension.and_then(|extension| self.language_settings.get(extension))
+                              .and_then(|settings| value(settings))
+                 })
+    }
+}
+
+impl BufferSettings {
+    fn parse(document: &Yaml) -> Result<BufferSettings, String> {
+        let line_length_guide = match document["line_length_guide"] {
+            Yaml::Integer(offset) if offset >= 0 => Some(offset as usize),
+            Yaml::Boolean(false) => Some(0),
+            Yaml::BadValue => None,
+            _ => {
+                return Err("line_length_guide must be a column number, or false.".to_string())
+            }
+        };
+
+        Ok(BufferSettings {
+            soft_tabs: try!(boolean(document, "soft_tabs")),
+            tab_width: try!(integer(document, "tab_width")),
+            line_length_guide: line_length_guide,
+            trim_trailing_whitespace: try!(boolean(document, "trim_trailing_whitespace")),
+            ensure_trailing_newline: try!(boolean(document, "ensure_trailing_newline")),
+        })
+    }
+}
+
+/// Builds settings for each of the languages with indentation conventions.
+fn language_settings() -> HashMap<String, BufferSettings> {
+    LANGUAGE_INDENTATION.iter()
+                        .map(|&(extension, indentation)| {
+                            let settings = match indentation {
+                                Indentation::Tabs => {
+                                    BufferSettings { soft_tabs: Some(false), ..Default::default() }
+                                }
+                                Indentation::Spaces(width) => {
+                                    BufferSettings {
+                                        soft_tabs: Some(true),
+                                        tab_width: Some(width),
+                                        ..Default::default()
+                                    }
+                                }
+                            };
+
+                            (extension.to_string(), settings)
+                        })
+                        .collect()
+}
+
+fn integer(document: &Yaml, key: &str) -> Result<Option<usize>, String> {
+    match document[key] {
+        Yaml::Integer(value) if value > 0 => Ok(Some(value as usize)),
+        Yaml::BadValue => Ok(None),
+        _ => Err(format!("{} must be a positive number.", key)),
+    }
+}
+
+fn boolean(document: &Yaml, key: &str) -> Result<Option<bool>, String> {
+    match document[key] {
+        Yaml::Boolean(value) => Ok(Some(value)),
+        Yaml::BadValue => Ok(None),
+        _ => Err(format!("{} must be either true or false.", key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use helpers::indentation::Indentation;
+    use view::Theme;
+    use super::Preferences;
+
+    #[test]
+    fn defaults_are_used_without_any_settings() {
+        let preferences = Preferences::parse("").unwrap();
+        let path = PathBuf::from("notes.txt");
+
+        assert_eq!(preferences.theme(), Theme::Dark);
+        assert!(!preferences.persistent_undo());
+        assert_eq!(preferences.clipboard(), "auto");
+        assert_eq!(preferences.tab_width(Some(&path)), 2);
+        assert_eq!(preferences.line_length_guide(None), Some(80));
+        assert!(preferences.trim_trailing_whitespace(Some(&path)));
+    }
+
+    #[test]
+    fn file_type_settings_override_general_settings() {
+        let preferences = Preferences::parse("theme: light\n\
+                                              tab_width: 4\n\
+                                              types:\n  \
+                                                md:\n    \
+                                                  line_length_guide: false\n    \
+                                                  tab_width: 3\n")
+                              .unwrap();
+        let markdown_path = PathBuf::from("README.md");
+        let text_path = PathBuf::from("notes.txt");
+
+        assert_eq!(preferences.theme(), Theme::Light);
+        assert_eq!(preferences.tab_width(Some(&markdown_path)), 3);
+        assert_eq!(preferences.tab_width(Some(&text_path)), 4);
+        assert_eq!(preferences.line_length_guide(Some(&markdown_path)), None);
+        assert_eq!(preferences.line_length_guide(Some(&text_path)), Some(80));
+    }
+
+    #[test]
+    fn language_conventions_are_used_unless_overridden_by_user_settings() {
+        let defaults = Preferences::parse("").unwrap();
+        assert_eq!(defaults.indentation(Some(&PathBuf::from("main.rs"))),
+                   Indentation::Spaces(4));
+        assert_eq!(defaults.indentation(Some(&PathBuf::from("notes.txt"))),
+                   Indentation::Spaces(2));
+
+        let preferences = Preferences::parse("tab_width: 3\n\
+                                              types:\n  \
+                                                py:\n    \
+                                                  soft_tabs: false\n")
+                              .unwrap();
+
+        // General settings take precedence over conventions, but
+        // conventions still fill in whatever the user hasn't set.
+        assert_eq!(preferences.indentation(Some(&PathBuf::from("main.go"))),
+                   Indentation::Tabs);
+        assert_eq!(preferences.indentation(Some(&PathBuf::from("main.rs"))),
+                   Indentation::Spaces(3));
+        assert_eq!(preferences.indentation(Some(&PathBuf::from("main.py"))),
+                   Indentation::Tabs);
+        assert_eq!(preferences.indentation(Some(&PathBuf::from("notes.txt"))),
+                   Indentation::Spaces(3));
+        assert_eq!(preferences.indentation(None), Indentation::Spaces(3));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_settings() {
+        assert!(Preferences::parse("tab_width: wide").is_err());
+        assert!(Preferences::parse("clipboard: carrier_pigeon").is_err());
+        assert!(Preferences::parse("types:\n  rs:\n    ensure_trailing_newline: 1").is_err());
+    }
+}
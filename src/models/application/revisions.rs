@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+/// Counts the changes made to each buffer, keyed by buffer ID, so that anything
+/// based on a buffer's content (e.g. its undo tree) can cheaply tell whether it's
+/// out of date, without comparing content. Commands report the changes they make.
+pub struct Revisions {
+    counts: HashMap<usize, usize>,
+}
+
+impl Revisions {
+    pub fn new() -> Revisions {
+        Revisions { counts: HashMap::new() }
+    }
+
+    /// The buffer's revision, which increases every time it's changed.
+    pub fn get(&self, buffer_id: usize) -> usize {
+        self.counts.get(&buffer_id).map(|count| *count).unwrap_or(0)
+    }
+
+    pub fn increment(&mut self, buffer_id: usize) {
+        *self.counts.entry(buffer_id).or_insert(0) += 1;
+    }
+}
@@ -0,0 +1,284 @@
+extern crate scribe;
+extern crate yaml_rust;
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use helpers;
+use helpers::directories;
+use helpers::indentation;
+use models::application::{Application, ClipboardContent, Message};
+use models::application::undo_tree;
+use scribe::Buffer;
+use scribe::buffer::Position;
+use self::yaml_rust::{Yaml, YamlEmitter, YamlLoader};
+use self::yaml_rust::yaml::Hash;
+
+/// The state of the editor in a workspace, which is saved on exit
+/// and restored the next time it's launched there without arguments.
+#[derive(Debug, PartialEq)]
+pub struct Session {
+    pub buffers: Vec<BufferSession>,
+
+    /// The index of the current buffer, in the list above.
+    pub current_buffer: Option<usize>,
+    pub search_query: Option<String>,
+    pub search_regex: bool,
+    pub clipboard: ClipboardContent,
+}
+
+/// Where a buffer's cursor and scrolling position were left.
+#[derive(Debug, PartialEq)]
+pub struct BufferSession {
+    pub path: PathBuf,
+    pub cursor: Position,
+    pub line_offset: usize,
+}
+
+impl Session {
+    /// Captures the application's state. Buffers without paths are left out.
+    pub fn capture(app: &mut Application) -> Session {
+        let mut buffers = Vec::new();
+        let mut current_buffer = None;
+        let current_id = app.workspace.current_buffer().and_then(|buffer| buffer.id);
+        let view = &mut app.view;
+        helpers::workspace::each_buffer(&mut app.workspace, |buffer| {
+            if let Some(ref path) = buffer.path {
+                if buffer.id == current_id {
+                    current_buffer = Some(buffers.len());
+                }
+                buffers.push(BufferSession {
+                    path: path.clone(),
+                    cursor: *buffer.cursor,
+                    line_offset: view.visible_region(buffer).line_offset(),
+                });
+            }
+        });
+
+        let clipboard = match *app.clipboard.get_content() {
+            ClipboardContent::Inline(ref content) => ClipboardContent::Inline(content.clone()),
+            ClipboardContent::Block(ref content) => ClipboardContent::Block(content.clone()),
+            ClipboardContent::None => ClipboardContent::None,
+        };
+
+        Session {
+            buffers: buffers,
+            current_buffer: current_buffer,
+            search_query: app.search_query.clone(),
+            search_regex: app.search_regex,
+            clipboard: clipboard,
+        }
+    }
+
+    /// Reopens the session's buffers (skipping any that can no longer be
+    /// read), and restores their positions, the search, and the clipboard.
+    pub fn restore(self, app: &mut Application) {
+        let workspace_path = app.workspace.path.clone();
+        let persistent = app.preferences.persistent_undo();
+        let mut current_id = None;
+        for (index, buffer_session) in self.buffers.into_iter().enumerate() {
+            let mut buffer = match Buffer::from_file(buffer_session.path) {
+                Ok(buffer) => buffer,
+                Err(_) => continue,
+            };
+            buffer.cursor.move_to(buffer_session.cursor);
+            app.workspace.add_buffer(buffer);
+
+            if let Some(buffer) = app.workspace.current_buffer() {
+                if let Some(ref path) = buffer.path {
+                    app.disk_states.record(path);
+                }
+                if let Some(id) = buffer.id {
+                    app.indentations.insert(id, indentation::detect(&buffer.data()));
+                    if let Err(error) = undo_tree::start(&mut app.undo_trees,
+                                                         buffer,
+                                                         app.revisions.get(id),
+                                                         &workspace_path,
+                                                         persistent) {
+                        app.messages.push(Message::error(error));
+                    }
+                }
+                app.view.scroll_to(buffer, buffer_session.line_offset);
+                if self.current_buffer == Some(index) {
+                    current_id = buffer.id;
+                }
+            }
+        }
+        if let Some(id) = current_id {
+            helpers::workspace::select_buffer(&mut app.workspace, id);
+        }
+
+        app.search_query = self.search_query;
+        app.search_regex = self.search_regex;
+        app.clipboard.restore_content(self.clipboard);
+    }
+
+    /// Loads the workspace's session, if one was saved.
+    pub fn load(workspace_path: &Path) -> Result<Option<Session>, String> {
+        let path = match session_path(workspace_path) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = try!(helpers::read_file(&path).map_err(|e| e.to_string()));
+        Session::parse(&data).map(|session| Some(session))
+    }
+
+    /// Saves the session for the workspace, replacing any previous one.
+    pub fn save(&self, workspace_path: &Path) -> Result<(), String> {
+        let path = match session_path(workspace_path) {
+            Some(path) => path,
+            None => return Err("Couldn't find a directory to save the session to.".to_string()),
+        };
+        if let Some(directory) = path.parent() {
+            try!(fs::create_dir_all(directory).map_err(|e| e.to_string()));
+        }
+
+        let data = try!(self.to_yaml());
+        File::create(&path)
+            .and_then(|mut file| file.write_all(data.as_bytes()))
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn parse(data: &str) -> Result<Session, String> {
+        let documents = try!(YamlLoader::load_from_str(data).map_err(|e| e.to_string()));
+        let document = match documents.first() {
+            Some(document) => document,
+            None => return Err("The session is empty.".to_string()),
+        };
+
+        let mut buffers = Vec::new();
+        if let Some(buffer_documents) = document["buffers"].as_vec() {
+            for buffer_document in buffer_documents {
+                let path = match buffer_document["path"].as_str() {
+                    Some(path) => PathBuf::from(path),
+                    None => return Err("Session buffers must have a path.".to_string()),
+                };
+                buffers.push(BufferSession {
+                    path: path,
+                    cursor: Position {
+                        line: integer(&buffer_document["line"]),
+                        offset: integer(&buffer_document["offset"]),
+                    },
+                    line_offset: integer(&buffer_document["line_offset"]),
+                });
+            }
+        }
+
+        let clipboard_content = document["clipboard"]["content"].as_str().map(|c| c.to_string());
+        let clipboard = match (document["clipboard"]["type"].as_str(), clipboard_content) {
+            (Some("inline"), Some(content)) => ClipboardContent::Inline(content),
+            (Some("block"), Some(content)) => ClipboardContent::Block(content),
+            _ => ClipboardContent::None,
+        };
+
+        Ok(Session {
+            buffers: buffers,
+            current_buffer: document["current_buffer"].as_i64().map(|index| index as usize),
+            search_query: document["search_query"].as_str().map(|query| query.to_string()),
+            search_regex: document["search_regex"].as_bool().unwrap_or(false),
+            clipboard: clipboard,
+        })
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        let buffers = self.buffers
+                          .iter()
+                          .map(|buffer| {
+                              let mut hash = Hash::new();
+                              insert(&mut hash, "path", string(&buffer.path.to_string_lossy()));
+                              insert(&mut hash, "line", Yaml::Integer(buffer.cursor.line as i64));
+                              insert(&mut hash,
+                                     "offset",
+                                     Yaml::Integer(buffer.cursor.offset as i64));
+                              insert(&mut hash,
+                                     "line_offset",
+                                     Yaml::Integer(buffer.line_offset as i64));
+                              Yaml::Hash(hash)
+                          })
+                          .collect();
+
+        let mut document = Hash::new();
+        insert(&mut document, "buffers", Yaml::Array(buffers));
+        if let Some(index) = self.current_buffer {
+            insert(&mut document, "current_buffer", Yaml::Integer(index as i64));
+        }
+        if let Some(ref query) = self.search_query {
+            insert(&mut document, "search_query", string(query));
+        }
+        insert(&mut document, "search_regex", Yaml::Boolean(self.search_regex));
+
+        let clipboard = match self.clipboard {
+            ClipboardContent::Inline(ref content) => Some(("inline", content)),
+            ClipboardContent::Block(ref content) => Some(("block", content)),
+            ClipboardContent::None => None,
+        };
+        if let Some((clipboard_type, content)) = clipboard {
+            let mut hash = Hash::new();
+            insert(&mut hash, "type", string(clipboard_type));
+            insert(&mut hash, "content", string(content));
+            insert(&mut document, "clipboard", Yaml::Hash(hash));
+        }
+
+        let mut data = String::new();
+        {
+            let mut emitter = YamlEmitter::new(&mut data);
+            try!(emitter.dump(&Yaml::Hash(document)).map_err(|e| format!("{:?}", e)));
+        }
+
+        Ok(data)
+    }
+}
+
+/// Sessions are kept in the data directory, named after their workspace's path.
+fn session_path(workspace_path: &Path) -> Option<PathBuf> {
+    directories::data().map(|directory| {
+        directory.join("sessions")
+                 .join(format!("{}.yml", directories::file_name_for(workspace_path)))
+    })
+}
+
+fn insert(hash: &mut Hash, key: &str, value: Yaml) {
+    hash.insert(string(key), value);
+}
+
+fn string(value: &str) -> Yaml {
+    Yaml::String(value.to_string())
+}
+
+fn integer(value: &Yaml) -> usize {
+    value.as_i64().map(|value| value as usize).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use std::path::PathBuf;
+    use models::application::ClipboardContent;
+    use scribe::buffer::Position;
+    use super::{BufferSession, Session};
+
+    #[test]
+    fn sessions_survive_a_round_trip_through_yaml() {
+        let session = Session {
+            buffers: vec![BufferSession {
+                              path: PathBuf::from("src/main.rs"),
+                              cursor: Position {
+                                  line: 10,
+                                  offset: 4,
+                              },
+                              line_offset: 2,
+                          }],
+            current_buffer: Some(0),
+            search_query: Some("amp: editor".to_string()),
+            search_regex: true,
+            clipboard: ClipboardContent::Block("amp\neditor\n".to_string()),
+        };
+
+        assert_eq!(Session::parse(&session.to_yaml().unwrap()).unwrap(), session);
+    }
+}
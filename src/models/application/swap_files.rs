@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use helpers;
+use helpers::directories;
+
+/// The number of keypresses between writing swap files.
+pub const WRITE_INTERVAL: usize = 50;
+
+/// Keeps copies of modified buffers' content in a cache directory,
+/// so that unsaved work can be recovered if the application crashes.
+/// Files are identified by their absolute paths, and buffers that don't
+/// have one yet by a path made up for them (see untitled_path).
+pub struct SwapFiles {
+    directory: Option<PathBuf>,
+    keypresses: usize,
+
+    /// Distinguishes this instance's made-up paths from those of others.
+    instance: u64,
+
+    /// Hashes of the content last written to each swap file.
+    written: HashMap<PathBuf, u64>,
+
+    /// Files that have already been checked for a swap file to recover.
+    checked: HashSet<PathBuf>,
+}
+
+impl SwapFiles {
+    pub fn new(directory: Option<PathBuf>) -> SwapFiles {
+        // Instances started at different times don't share made-up paths.
+        let started = SystemTime::now()
+                          .duration_since(UNIX_EPOCH)
+                          .map(|duration| duration.as_secs() ^ duration.subsec_nanos() as u64)
+                          .unwrap_or(0);
+
+        SwapFiles {
+            directory: directory,
+            keypresses: 0,
+            instance: started,
+            written: HashMap::new(),
+            checked: HashSet::new(),
+        }
+    }
+
+    /// Counts a keypress, returning whether it's time to write swap files.
+    pub fn tick(&mut self) -> bool {
+        self.keypresses += 1;
+        self.keypresses % WRITE_INTERVAL == 0
+    }
+
+    /// The path identifying the swap file of the buffer with the specified ID,
+    /// for buffers that don't have a path of their own. Their swap files
+    /// can't be recovered automatically, but are kept in the swap directory.
+    pub fn untitled_path(&self, buffer_id: usize) -> PathBuf {
+        PathBuf::from(format!("untitled-{:x}-{}", self.instance, buffer_id))
+    }
+
+    /// Writes the content to the file's swap file, unless it's already there.
+    /// The content is written to a temporary file that then replaces the swap
+    /// file, so that a crash partway through writing can't leave it truncated.
+    pub fn write(&mut self, path: &Path, data: &str) -> io::Result<()> {
+        let swap_path = match self.swap_path(path) {
+            Some(swap_path) => swap_path,
+            None => return Ok(()),
+        };
+
+        let hash = hash(data);
+        if self.written.get(path) == Some(&hash) {
+            return Ok(());
+        }
+
+        if let Some(directory) = swap_path.parent() {
+            try!(fs::create_dir_all(directory));
+        }
+        let temporary_path = swap_path.with_extension("swp.tmp");
+        let mut file = try!(File::create(&temporary_path));
+        try!(file.write_all(data.as_bytes()));
+        try!(file.sync_all());
+        try!(fs::rename(&temporary_path, &swap_path));
+        self.written.insert(path.to_path_buf(), hash);
+
+        Ok(())
+    }
+
+    /// Removes the file's swap file, if it has one.
+    pub fn remove(&mut self, path: &Path) {
+        self.written.remove(path);
+        if let Some(swap_path) = self.swap_path(path) {
+            let _ = fs::remove_file(swap_path);
+        }
+    }
+
+    /// Removes every swap file written by this instance of the application.
+    pub fn remove_all(&mut self) {
+        let paths: Vec<PathBuf> = self.written.keys().cloned().collect();
+        for path in paths {
+            self.remove(&path);
+        }
+    }
+
+    /// Looks for a swap file left behind for the file (i.e. by a crash), with
+    /// content that differs from the file, returning its path. Each file is
+    /// only checked once, so that declining to recover it isn't asked again.
+    pub fn recoverable(&mut self, path: &Path) -> Option<PathBuf> {
+        if !self.checked.insert(path.to_path_buf()) {
+            return None;
+        }
+
+        let swap_path = match self.swap_path(path) {
+            Some(swap_path) => swap_path,
+            None => return None,
+        };
+        let swap_data = match helpers::read_file(&swap_path) {
+            Ok(data) => data,
+            Err(_) => return None,
+        };
+
+        // Swap files matching their file are of no use.
+        if helpers::read_file(path).ok() == Some(swap_data) {
+            let _ = fs::remove_file(&swap_path);
+            return None;
+        }
+
+        // Swap files older than their file predate the last save.
+        let file_modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        let swap_modified = fs::metadata(&swap_path).and_then(|metadata| metadata.modified()).ok();
+        match (file_modified, swap_modified) {
+            (Some(file_modified), Some(swap_modified)) if swap_modified < file_modified => None,
+            _ => Some(swap_path),
+        }
+    }
+
+    /// Where the file's swap file is kept: its absolute path, flattened into a
+    /// file name, in the swap directory (e.g. "%2Fhome%2Famp%2Fnotes.txt.swp").
+    fn swap_path(&self, path: &Path) -> Option<PathBuf> {
+        self.directory.as_ref().map(|directory| {
+            directory.join(format!("{}.swp", directories::file_name_for(path)))
+        })
+    }
+}
+
+fn hash(data: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use super::SwapFiles;
+
+    #[test]
+    fn recoverable_finds_swap_files_with_unsaved_content_once() {
+        let directory = env::temp_dir().join("amp_swap_files_test");
+        let path = env::temp_dir().join("amp_swap_files_test.txt");
+        File::create(&path).unwrap().write_all(b"amp").unwrap();
+
+        // Leave a swap file behind, as a crash would.
+        SwapFiles::new(Some(directory.clone())).write(&path, "amp editor").unwrap();
+
+        let mut swap_files = SwapFiles::new(Some(directory.clone()));
+        assert!(swap_files.recoverable(&path).is_some());
+        assert!(swap_files.recoverable(&path).is_none());
+
+        swap_files.remove(&path);
+        assert!(SwapFiles::new(Some(directory.clone())).recoverable(&path).is_none());
+
+        fs::remove_file(&path).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
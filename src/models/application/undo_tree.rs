@@ -0,0 +1,777 @@
+extern crate scribe;
+extern crate yaml_rust;
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use helpers;
+use helpers::directories;
+use scribe::Buffer;
+use scribe::buffer::Position;
+use self::yaml_rust::{Yaml, YamlEmitter, YamlLoader};
+use self::yaml_rust::yaml::Hash;
+
+/// A buffer's edit history. Unlike a linear undo stack, making changes after
+/// undoing starts a new branch, rather than discarding the undone changes, so
+/// that every state the buffer has been in can be returned to. States only keep
+/// the change that was made to get to them; the tree holds onto the content of
+/// the current state alone, which new changes are found against.
+#[derive(Debug, PartialEq)]
+pub struct UndoTree {
+    states: Vec<UndoState>,
+    current: usize,
+    data: String,
+
+    /// The revision of the buffer (see `Revisions`) that the tree was last
+    /// brought up to date with, so that unchanged buffers aren't compared.
+    pub revision: usize,
+}
+
+/// A state a buffer has been in, and where its cursor was left.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndoState {
+    /// The change that turned the parent state's content into this one's.
+    /// The first state doesn't have one.
+    pub change: Option<Change>,
+    pub cursor: Position,
+
+    /// When the state was recorded, in seconds since the Unix epoch.
+    pub timestamp: u64,
+
+    /// The state this one was changed from; only the first state has none.
+    pub parent: Option<usize>,
+
+    /// The child state that redoing moves to: the one most recently visited.
+    redo_child: Option<usize>,
+}
+
+/// Text replaced at a position: the smallest span of a buffer's
+/// content covering everything that differs between two states.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub position: Position,
+    pub removed: String,
+    pub inserted: String,
+}
+
+impl Change {
+    /// Finds the change that turns the old content into the new content, if they differ.
+    pub fn between(old: &str, new: &str) -> Option<Change> {
+        if old == new {
+            return None;
+        }
+
+        // Skip past the content that both start with...
+        let mut prefix_length = 0;
+        for (old_character, new_character) in old.chars().zip(new.chars()) {
+            if old_character != new_character {
+                break;
+            }
+            prefix_length += old_character.len_utf8();
+        }
+
+        // ...and end with, without overlapping the start.
+        let old_rest = &old[prefix_length..];
+        let new_rest = &new[prefix_length..];
+        let suffix_length = old_rest.chars()
+                                    .rev()
+                                    .zip(new_rest.chars().rev())
+                                    .take_while(|&(old_character, new_character)| {
+                                        old_character == new_character
+                                    })
+                                    .fold(0, |length, (character, _)| {
+                                        length + character.len_utf8()
+                                    });
+
+        Some(Change {
+            position: end_of(Position {
+                                 line: 0,
+                                 offset: 0,
+                             },
+                             &old[..prefix_length]),
+            removed: old_rest[..old_rest.len() - suffix_length].to_string(),
+            inserted: new_rest[..new_rest.len() - suffix_length].to_string(),
+        })
+    }
+
+    /// The change that takes this one back.
+    pub fn reversed(&self) -> Change {
+        Change {
+            position: self.position,
+            removed: self.inserted.clone(),
+            inserted: self.removed.clone(),
+        }
+    }
+
+    /// Where the removed text ends.
+    pub fn removed_end(&self) -> Position {
+        end_of(self.position, &self.removed)
+    }
+
+    /// Makes the change to the content, which is expected to be the content
+    /// it was found against (or that its reversal produced). Returns None if
+    /// the content doesn't have the removed text at the change's position.
+    pub fn apply_to(&self, data: &str) -> Option<String> {
+        let start = match byte_index(data, self.position) {
+            Some(start) if data[start..].starts_with(&self.removed) => start,
+            _ => return None,
+        };
+        let end = start + self.removed.len();
+
+        let mut result = String::with_capacity(data.len() + self.inserted.len());
+        result.push_str(&data[..start]);
+        result.push_str(&self.inserted);
+        result.push_str(&data[end..]);
+
+        Some(result)
+    }
+}
+
+impl UndoTree {
+    pub fn new(data: String, cursor: Position) -> UndoTree {
+        UndoTree {
+            states: vec![UndoState {
+                             change: None,
+                             cursor: cursor,
+                             timestamp: now(),
+                             parent: None,
+                             redo_child: None,
+                         }],
+            current: 0,
+            data: data,
+            revision: 0,
+        }
+    }
+
+    /// The states, in the order they were recorded.
+    pub fn states(&self) -> &[UndoState] {
+        &self.states
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> &UndoState {
+        &self.states[self.current]
+    }
+
+    /// The buffer's content in the current state.
+    pub fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Adds a state as a child of the current one, unless its content is
+    /// unchanged, returning whether it was added. The new state becomes the
+    /// current one, leaving any previously undone states on their own branch.
+    pub fn record(&mut self, data: String, cursor: Position) -> bool {
+        let change = match Change::between(&self.data, &data) {
+            Some(change) => change,
+            None => return false,
+        };
+
+        let index = self.states.len();
+        self.states.push(UndoState {
+            change: Some(change),
+            cursor: cursor,
+            timestamp: now(),
+            parent: Some(self.current),
+            redo_child: None,
+        });
+        self.states[self.current].redo_child = Some(index);
+        self.current = index;
+        self.data = data;
+
+        true
+    }
+
+    /// Moves to the current state's parent, returning the changes that take the
+    /// buffer's content there, in the order they're to be made (if any).
+    pub fn undo(&mut self) -> Vec<Change> {
+        let parent = self.current().parent;
+        match parent {
+            Some(parent) => self.move_to(parent),
+            None => Vec::new(),
+        }
+    }
+
+    /// Moves to the most recently visited child of the current state,
+    /// returning the changes that take the buffer's content there.
+    pub fn redo(&mut self) -> Vec<Change> {
+        let redo_child = self.current().redo_child;
+        match redo_child {
+            Some(child) => self.move_to(child),
+            None => Vec::new(),
+        }
+    }
+
+    /// Moves to the latest state of the branch recorded after the current
+    /// one's (a branch being a line of history ending in a state that hasn't
+    /// been changed since), returning the changes that take the buffer's
+    /// content there.
+    pub fn next_branch(&mut self) -> Vec<Change> {
+        let tip = self.branch_tip();
+        match self.branch_tips().into_iter().find(|&index| index > tip) {
+            Some(index) => self.select(index),
+            None => Vec::new(),
+        }
+    }
+
+    /// Moves to the latest state of the branch recorded before the current
+    /// one's, returning the changes that take the buffer's content there.
+    pub fn previous_branch(&mut self) -> Vec<Change> {
+        let tip = self.branch_tip();
+        match self.branch_tips().into_iter().rev().find(|&index| index < tip) {
+            Some(index) => self.select(index),
+            None => Vec::new(),
+        }
+    }
+
+    /// Moves to the specified state, so that undoing and redoing retrace the
+    /// path to it, returning the changes that take the buffer's content there.
+    pub fn select(&mut self, index: usize) -> Vec<Change> {
+        if index >= self.states.len() {
+            return Vec::new();
+        }
+
+        // Point each of the state's ancestors towards it.
+        let mut child = index;
+        while let Some(parent) = self.states[child].parent {
+            self.states[parent].redo_child = Some(child);
+            child = parent;
+        }
+
+        self.move_to(index)
+    }
+
+    /// Makes the specified state the current one, returning the changes that
+    /// take the content there: back up to the closest state the two have in
+    /// common, and then forward again to the specified one.
+    fn move_to(&mut self, index: usize) -> Vec<Change> {
+        let mut path = vec![index];
+        let mut state = index;
+        while let Some(parent) = self.states[state].parent {
+            path.push(parent);
+            state = parent;
+        }
+
+        let mut changes = Vec::new();
+        let mut ancestor = self.current;
+        while !path.contains(&ancestor) {
+            if let Some(ref change) = self.states[ancestor].change {
+                changes.push(change.reversed());
+            }
+            match self.states[ancestor].parent {
+                Some(parent) => ancestor = parent,
+                None => break,
+            }
+        }
+
+        let common_states = path.iter().position(|&state| state == ancestor).unwrap_or(0);
+        for &state in path[..common_states].iter().rev() {
+            if let Some(ref change) = self.states[state].change {
+                changes.push(change.clone());
+            }
+        }
+
+        // Loaded histories have been checked, so every change applies.
+        for change in changes.iter() {
+            if let Some(data) = change.apply_to(&self.data) {
+                self.data = data;
+            }
+        }
+        self.current = index;
+
+        changes
+    }
+
+    /// The latest state on the current branch, found by redoing as far as possible.
+    fn branch_tip(&self) -> usize {
+        let mut index = self.current;
+        while let Some(child) = self.states[index].redo_child {
+            index = child;
+        }
+
+        index
+    }
+
+    /// The states that haven't been changed since, in the order they were recorded.
+    fn branch_tips(&self) -> Vec<usize> {
+        (0..self.states.len())
+            .filter(|&index| self.states.iter().all(|state| state.parent != Some(index)))
+            .collect()
+    }
+
+    /// Loads the history saved for the file, if there is one, and it was
+    /// saved with the specified content (i.e. the file hasn't changed since).
+    pub fn load(path: &Path, data: &str) -> Result<Option<UndoTree>, String> {
+        let history_path = match history_path(path) {
+            Some(history_path) => history_path,
+            None => return Ok(None),
+        };
+        if !history_path.exists() {
+            return Ok(None);
+        }
+
+        let history = try!(helpers::read_file(&history_path).map_err(|e| e.to_string()));
+        UndoTree::parse(&history, data)
+    }
+
+    /// Saves the history for the file, replacing any previous one.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let history_path = match history_path(path) {
+            Some(history_path) => history_path,
+            None => return Err("Couldn't find a directory to save the history to.".to_string()),
+        };
+        if let Some(directory) = history_path.parent() {
+            try!(fs::create_dir_all(directory).map_err(|e| e.to_string()));
+        }
+
+        let history = try!(self.to_yaml());
+        File::create(&history_path)
+            .and_then(|mut file| file.write_all(history.as_bytes()))
+            .map_err(|e| e.to_string())
+    }
+
+    /// Builds a tree from its YAML representation, with the specified content
+    /// for its current state, or None if it was saved with different content.
+    pub fn parse(history: &str, data: &str) -> Result<Option<UndoTree>, String> {
+        let documents = try!(YamlLoader::load_from_str(history).map_err(|e| e.to_string()));
+        let document = match documents.first() {
+            Some(document) => document,
+            None => return Err("The history is empty.".to_string()),
+        };
+
+        if document["checksum"].as_i64() != Some(checksum(data) as i64) {
+            return Ok(None);
+        }
+
+        let mut states = Vec::new();
+        if let Some(state_documents) = document["states"].as_vec() {
+            for (index, state_document) in state_documents.iter().enumerate() {
+                // States can only refer to those recorded before them.
+                let parent = index_at(&state_document["parent"]);
+                if index > 0 && parent.map(|parent| parent >= index).unwrap_or(true) {
+                    return Err("History states must have an earlier parent.".to_string());
+                }
+
+                let change = if index == 0 {
+                    None
+                } else {
+                    match (state_document["removed"].as_str(),
+                           state_document["inserted"].as_str()) {
+                        (Some(removed), Some(inserted)) => {
+                            Some(Change {
+                                position: Position {
+                                    line: index_at(&state_document["change_line"]).unwrap_or(0),
+                                    offset: index_at(&state_document["change_offset"])
+                                                .unwrap_or(0),
+                                },
+                                removed: removed.to_string(),
+                                inserted: inserted.to_string(),
+                            })
+                        }
+                        _ => return Err("History states must have a change.".to_string()),
+                    }
+                };
+
+                states.push(UndoState {
+                    change: change,
+                    cursor: Position {
+                        line: index_at(&state_document["line"]).unwrap_or(0),
+                        offset: index_at(&state_document["offset"]).unwrap_or(0),
+                    },
+                    timestamp: state_document["timestamp"].as_i64().unwrap_or(0) as u64,
+                    parent: if index == 0 { None } else { parent },
+                    redo_child: None,
+                });
+            }
+        }
+
+        let current = match index_at(&document["current"]) {
+            Some(current) if current < states.len() => current,
+            _ => return Err("The history's current state is missing.".to_string()),
+        };
+
+        // Rebuild the redo path from the most recently recorded children.
+        for index in 1..states.len() {
+            if let Some(parent) = states[index].parent {
+                states[parent].redo_child = Some(index);
+            }
+        }
+
+        // A history saved with this content could still have been corrupted
+        // since; make sure that every one of its changes can be made.
+        try!(check_changes(&states, current, data));
+
+        let mut tree = UndoTree {
+            states: states,
+            current: current,
+            data: data.to_string(),
+            revision: 0,
+        };
+        tree.select(current);
+
+        Ok(Some(tree))
+    }
+
+    pub fn to_yaml(&self) -> Result<String, String> {
+        let states = self.states
+                         .iter()
+                         .map(|state| {
+                             let mut hash = Hash::new();
+                             if let Some(ref change) = state.change {
+                                 insert(&mut hash,
+                                        "change_line",
+                                        Yaml::Integer(change.position.line as i64));
+                                 insert(&mut hash,
+                                        "change_offset",
+                                        Yaml::Integer(change.position.offset as i64));
+                                 insert(&mut hash, "removed", Yaml::String(change.removed.clone()));
+                                 insert(&mut hash,
+                                        "inserted",
+                                        Yaml::String(change.inserted.clone()));
+                             }
+                             insert(&mut hash, "line", Yaml::Integer(state.cursor.line as i64));
+                             insert(&mut hash, "offset", Yaml::Integer(state.cursor.offset as i64));
+                             insert(&mut hash, "timestamp", Yaml::Integer(state.timestamp as i64));
+                             if let Some(parent) = state.parent {
+                                 insert(&mut hash, "parent", Yaml::Integer(parent as i64));
+                             }
+                             Yaml::Hash(hash)
+                         })
+                         .collect();
+
+        let mut document = Hash::new();
+        insert(&mut document, "current", Yaml::Integer(self.current as i64));
+        insert(&mut document, "checksum", Yaml::Integer(checksum(&self.data) as i64));
+        insert(&mut document, "states", Yaml::Array(states));
+
+        let mut history = String::new();
+        {
+            let mut emitter = YamlEmitter::new(&mut history);
+            try!(emitter.dump(&Yaml::Hash(document)).map_err(|e| format!("{:?}", e)));
+        }
+
+        Ok(history)
+    }
+}
+
+/// Starts the buffer's undo tree, if it doesn't have one yet, picking up its
+/// saved history if persistent undo is enabled and the history matches its
+/// content. This is done as buffers are added, before they can be changed, so
+/// that their trees can take them back to the state they were opened in. If
+/// the saved history can't be read, the tree is started without it.
+pub fn start(trees: &mut HashMap<usize, UndoTree>,
+             buffer: &Buffer,
+             revision: usize,
+             workspace_path: &Path,
+             persistent: bool)
+             -> Result<(), String> {
+    let id = match buffer.id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+    if trees.contains_key(&id) {
+        return Ok(());
+    }
+
+    let data = buffer.data();
+    let (saved_tree, result) = match buffer.path {
+        Some(ref path) if persistent => {
+            match UndoTree::load(&workspace_path.join(path), &data) {
+                Ok(tree) => (tree, Ok(())),
+                Err(error) => {
+                    (None,
+                     Err(format!("Couldn't load the history for {}: {}",
+                                 path.to_string_lossy(),
+                                 error)))
+                }
+            }
+        }
+        _ => (None, Ok(())),
+    };
+
+    let mut tree = saved_tree.unwrap_or_else(|| UndoTree::new(data, *buffer.cursor));
+    tree.revision = revision;
+    trees.insert(id, tree);
+
+    result
+}
+
+/// The current time, in seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0)
+}
+
+/// Histories are kept in the data directory, named after their file's path.
+fn history_path(path: &Path) -> Option<PathBuf> {
+    directories::data().map(|directory| {
+        directory.join("history")
+                 .join(format!("{}.yml", directories::file_name_for(path)))
+    })
+}
+
+/// Identifies the content that a history was saved with, without keeping a copy
+/// of it. This is FNV-1a, rather than the standard library's hasher, whose
+/// algorithm can change between releases, taking saved histories with it.
+fn checksum(data: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in data.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Checks that the states' changes can be made, working back from the current
+/// state's content to the first state's, and then out to every other state.
+fn check_changes(states: &[UndoState], current: usize, data: &str) -> Result<(), String> {
+    let mismatch = || "The history doesn't match the file's content.".to_string();
+
+    let mut content = data.to_string();
+    let mut state = current;
+    while let Some(parent) = states[state].parent {
+        if let Some(ref change) = states[state].change {
+            content = try!(change.reversed().apply_to(&content).ok_or_else(&mismatch));
+        }
+        state = parent;
+    }
+
+    let mut children = vec![Vec::new(); states.len()];
+    for (index, state) in states.iter().enumerate() {
+        if let Some(parent) = state.parent {
+            children[parent].push(index);
+        }
+    }
+
+    // Visit each state from its parent's content, and take it back to
+    // that content once the state's children have been visited.
+    let mut pending = vec![(0, false)];
+    while let Some((index, visited)) = pending.pop() {
+        if let Some(ref change) = states[index].change {
+            let change = if visited { change.reversed() } else { change.clone() };
+            content = try!(change.apply_to(&content).ok_or_else(&mismatch));
+        }
+        if !visited {
+            pending.push((index, true));
+            pending.extend(children[index].iter().map(|&child| (child, false)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Where the text ends, if it starts at the specified position.
+fn end_of(start: Position, text: &str) -> Position {
+    let mut position = start;
+    for character in text.chars() {
+        if character == '\n' {
+            position.line += 1;
+            position.offset = 0;
+        } else {
+            position.offset += 1;
+        }
+    }
+
+    position
+}
+
+/// The byte index of the position in the content, if it's within it.
+fn byte_index(data: &str, position: Position) -> Option<usize> {
+    let mut current = Position {
+        line: 0,
+        offset: 0,
+    };
+    for (index, character) in data.char_indices() {
+        if current == position {
+            return Some(index);
+        }
+
+        if character == '\n' {
+            current.line += 1;
+            current.offset = 0;
+        } else {
+            current.offset += 1;
+        }
+    }
+
+    if current == position {
+        Some(data.len())
+    } else {
+        None
+    }
+}
+
+fn insert(hash: &mut Hash, key: &str, value: Yaml) {
+    hash.insert(Yaml::String(key.to_string()), value);
+}
+
+fn index_at(value: &Yaml) -> Option<usize> {
+    match value.as_i64() {
+        Some(value) if value >= 0 => Some(value as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate scribe;
+
+    use std::collections::HashMap;
+    use std::env;
+    use scribe::{Buffer, Workspace};
+    use scribe::buffer::Position;
+    use super::{Change, UndoTree, checksum, start};
+
+    fn position() -> Position {
+        Position {
+            line: 0,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn record_ignores_unchanged_content() {
+        let mut tree = UndoTree::new("amp".to_string(), position());
+
+        assert!(!tree.record("amp".to_string(), position()));
+        assert!(tree.record("amp editor".to_string(), position()));
+        assert_eq!(tree.states().len(), 2);
+    }
+
+    #[test]
+    fn changes_only_cover_the_text_that_differs() {
+        let change = Change::between("amp\neditor\n", "amp\ntext editor\n").unwrap();
+
+        assert_eq!(change,
+                   Change {
+                       position: Position {
+                           line: 1,
+                           offset: 0,
+                       },
+                       removed: String::new(),
+                       inserted: "text ".to_string(),
+                   });
+        assert_eq!(change.apply_to("amp\neditor\n"),
+                   Some("amp\ntext editor\n".to_string()));
+        assert_eq!(change.reversed().apply_to("amp\ntext editor\n"),
+                   Some("amp\neditor\n".to_string()));
+
+        // Content the change wasn't found against is left alone.
+        assert_eq!(change.reversed().apply_to("amp\neditor\n"), None);
+        assert_eq!(change.apply_to("amp"), None);
+        assert_eq!(Change::between("aaa", "aa").unwrap().removed, "a");
+    }
+
+    #[test]
+    fn changes_made_after_undoing_start_a_new_branch() {
+        let mut tree = UndoTree::new("amp".to_string(), position());
+        tree.record("amp editor".to_string(), position());
+        tree.undo();
+        tree.record("amp text editor".to_string(), position());
+
+        // The undone state is kept, though redoing follows the new branch.
+        tree.undo();
+        assert_eq!(tree.data(), "amp");
+        tree.redo();
+        assert_eq!(tree.data(), "amp text editor");
+
+        // Switching branches brings back the undone state.
+        assert_eq!(tree.previous_branch().len(), 2);
+        assert_eq!(tree.data(), "amp editor");
+        assert!(tree.previous_branch().is_empty());
+        assert!(!tree.next_branch().is_empty());
+        assert_eq!(tree.data(), "amp text editor");
+    }
+
+    #[test]
+    fn select_makes_redo_retrace_the_path_to_the_selected_state() {
+        let mut tree = UndoTree::new("amp".to_string(), position());
+        tree.record("amp editor".to_string(), position());
+        tree.undo();
+        tree.record("amp text editor".to_string(), position());
+
+        assert!(!tree.select(1).is_empty());
+        tree.undo();
+        tree.redo();
+        assert_eq!(tree.data(), "amp editor");
+        assert!(tree.select(3).is_empty());
+    }
+
+    #[test]
+    fn trees_survive_a_round_trip_through_yaml() {
+        let mut tree = UndoTree::new("amp".to_string(), position());
+        tree.record("amp editor".to_string(),
+                    Position {
+                        line: 0,
+                        offset: 4,
+                    });
+        tree.undo();
+        tree.record("amp\neditor\n".to_string(),
+                    Position {
+                        line: 1,
+                        offset: 0,
+                    });
+        tree.select(1);
+
+        let history = tree.to_yaml().unwrap();
+        let parsed_tree = UndoTree::parse(&history, tree.data()).unwrap();
+        assert_eq!(parsed_tree, Some(tree));
+    }
+
+    #[test]
+    fn parse_ignores_histories_saved_with_different_content() {
+        let tree = UndoTree::new("amp".to_string(), position());
+
+        assert_eq!(UndoTree::parse(&tree.to_yaml().unwrap(), "editor").unwrap(), None);
+    }
+
+    #[test]
+    fn parse_rejects_states_with_later_parents() {
+        let history = format!("current: 0\nchecksum: {}\nstates:\n  - line: 0\n  - removed: a\n    \
+                               inserted: b\n    parent: 1\n",
+                              checksum("amp") as i64);
+
+        assert!(UndoTree::parse(&history, "amp").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_changes_that_do_not_match_the_content() {
+        // The second state claims to have inserted text that isn't there.
+        let history = format!("current: 1\nchecksum: {}\nstates:\n  - line: 0\n  \
+                               - removed: \"\"\n    inserted: editor\n    change_offset: 9\n    \
+                               parent: 0\n",
+                              checksum("amp") as i64);
+
+        assert!(UndoTree::parse(&history, "amp").is_err());
+    }
+
+    #[test]
+    fn checksums_are_stable() {
+        assert_eq!(checksum(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(checksum("a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn start_keeps_the_tree_a_buffer_already_has() {
+        let workspace_path = env::current_dir().unwrap();
+        let mut workspace = Workspace::new(workspace_path.clone());
+        let mut buffer = Buffer::new();
+        buffer.insert("amp");
+        workspace.add_buffer(buffer);
+        let buffer = workspace.current_buffer().unwrap();
+        let id = buffer.id.unwrap();
+
+        let mut trees = HashMap::new();
+        start(&mut trees, buffer, 0, &workspace_path, false).unwrap();
+        buffer.insert("editor ");
+        start(&mut trees, buffer, 1, &workspace_path, false).unwrap();
+
+        assert_eq!(trees[&id].data(), "amp");
+        assert_eq!(trees[&id].revision, 0);
+    }
+}
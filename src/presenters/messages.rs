@@ -0,0 +1,37 @@
+extern crate rustbox;
+
+use models::application::{Message, MessageKind};
+use rustbox::Color;
+use view::{StatusLineData, View};
+
+/// Draws the latest message over the current mode's status line,
+/// noting how many others were raised alongside it, if any.
+pub fn display(messages: &Vec<Message>, view: &mut View) {
+    let message = match messages.last() {
+        Some(message) => message,
+        None => return,
+    };
+
+    let (background_color, foreground_color) = match message.kind {
+        MessageKind::Info => (None, None),
+        MessageKind::Warning => (Some(Color::Yellow), Some(Color::Black)),
+        MessageKind::Error => (Some(Color::Red), Some(Color::White)),
+    };
+
+    let content = match messages.len() {
+        1 => format!(" {}", message.content),
+        count => format!(" {} (+{} more)", message.content, count - 1),
+    };
+
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: content,
+            style: None,
+            background_color: background_color,
+            foreground_color: foreground_color,
+        }
+    ]);
+
+    // Render the changes to the screen.
+    view.present();
+}
@@ -2,6 +2,7 @@ extern crate git2;
 extern crate scribe;
 extern crate rustbox;
 
+pub mod messages;
 pub mod modes;
 
 use std::path::PathBuf;
@@ -57,6 +58,48 @@ fn relative_range(region: &ScrollableRegion, range: &Range) -> Range {
     Range::new(relative_start, relative_end)
 }
 
+/// Converts search results on visible lines to ranges relative to the scrolled
+/// buffer, discarding the rest. Results never span lines, so checking their
+/// start is enough to know that they're visible.
+fn visible_search_results(region: &ScrollableRegion, results: &Vec<Range>) -> Vec<Range> {
+    results.iter()
+           .filter(|range| region.visible_range().includes(range.start().line))
+           .map(|range| relative_range(region, range))
+           .collect()
+}
+
+/// Converts cursors on visible lines to positions relative
+/// to the scrolled buffer, discarding the rest.
+fn visible_cursors(region: &ScrollableRegion, cursors: &[Position]) -> Vec<Position> {
+    cursors.iter()
+           .filter_map(|cursor| {
+               match region.relative_position(cursor.line) {
+                   Visibility::Visible(line) => {
+                       Some(Position {
+                           line: line,
+                           offset: cursor.offset,
+                       })
+                   }
+                   _ => None,
+               }
+           })
+           .collect()
+}
+
+/// Describes the cursor's place among the search results (e.g. "2 of 5"),
+/// or the number of results if the cursor isn't at the start of one.
+fn search_result_counter(results: &Vec<Range>, cursor: &Position) -> String {
+    match results.iter().position(|range| range.start() == *cursor) {
+        Some(index) => format!("{} of {}", index + 1, results.len()),
+        None => {
+            match results.len() {
+                1 => "1 match".to_string(),
+                count => format!("{} matches", count),
+            }
+        }
+    }
+}
+
 fn line_count(data: &str) -> usize {
     data.chars().filter(|&c| c == '\n').count() + 1
 }
@@ -139,8 +182,8 @@ mod tests {
     extern crate git2;
     extern crate scribe;
 
-    use super::{line_count, presentable_status, visible_tokens};
-    use scribe::buffer::{Buffer, LineRange, Token, Category};
+    use super::{line_count, presentable_status, search_result_counter, visible_tokens};
+    use scribe::buffer::{Buffer, LineRange, Position, Range, Token, Category};
 
     #[test]
     fn visible_tokens_returns_tokens_in_the_specified_range() {
@@ -157,6 +200,19 @@ mod tests {
             ]);
     }
 
+    #[test]
+    fn search_result_counter_describes_the_result_at_the_cursor() {
+        let results = vec![
+            Range::new(Position{ line: 0, offset: 4 }, Position{ line: 0, offset: 6 }),
+            Range::new(Position{ line: 1, offset: 0 }, Position{ line: 1, offset: 2 }),
+        ];
+
+        assert_eq!(search_result_counter(&results, &Position{ line: 1, offset: 0 }),
+                   "2 of 2".to_string());
+        assert_eq!(search_result_counter(&results, &Position{ line: 1, offset: 1 }),
+                   "2 matches".to_string());
+    }
+
     #[test]
     fn line_count_returns_correct_count_with_trailing_newline() {
         let data = "amp\neditor\n";
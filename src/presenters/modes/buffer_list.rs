@@ -0,0 +1,102 @@
+extern crate rustbox;
+extern crate scribe;
+
+use models::application::modes::BufferListMode;
+use models::application::modes::buffer_list::MAX_RESULTS;
+use pad::PadStr;
+use presenters::{buffer_status_line_data, line_count, visible_tokens};
+use rustbox::Color;
+use view::{BufferData, StatusLineData, View};
+use scribe::buffer::{Buffer, Position};
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &BufferListMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+
+        // Draw the status line.
+        view.draw_status_line(&vec![
+            StatusLineData {
+                content: " BUFFERS ".to_string(),
+                style: None,
+                background_color: Some(Color::White),
+                foreground_color: Some(Color::Black)
+            },
+            buffer_status_line_data(&buf)
+        ]);
+    }
+
+    // Draw the page of buffers containing the selection, marking those
+    // with unsaved changes the same way the status line does.
+    let mut line = 0;
+    let selected_index = mode.results.selected_index();
+    let page_start = selected_index - selected_index % MAX_RESULTS;
+    for (index, entry) in mode.results.iter().enumerate().skip(page_start).take(MAX_RESULTS) {
+        let color = if index == selected_index {
+            view.alt_background_color()
+        } else {
+            Color::Default
+        };
+        let (content, style) = if entry.modified {
+            (format!("{}*", entry.title), rustbox::RB_BOLD)
+        } else {
+            (entry.title.clone(), rustbox::RB_NORMAL)
+        };
+        view.print(0,
+                   line,
+                   style,
+                   Color::Default,
+                   color,
+                   &content.pad_to_width(view.width()));
+        line += 1;
+    }
+
+    // Clear any remaining lines in the result display area.
+    for line in line..MAX_RESULTS {
+        view.print(0,
+                   line,
+                   rustbox::RB_NORMAL,
+                   Color::Default,
+                   Color::Default,
+                   &String::new().pad_to_width(view.width()));
+    }
+
+    // Draw the divider.
+    let line = MAX_RESULTS;
+    let padded_content = mode.input.pad_to_width(view.width());
+    view.print(0,
+               line,
+               rustbox::RB_BOLD,
+               Color::Black,
+               Color::White,
+               &padded_content);
+
+    // Place the cursor on the search input line, right after its contents.
+    view.set_cursor(Some(Position {
+        line: MAX_RESULTS,
+        offset: mode.input.chars().count(),
+    }));
+
+    // Render the changes to the screen.
+    view.present();
+}
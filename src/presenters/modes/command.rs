@@ -0,0 +1,109 @@
+extern crate rustbox;
+extern crate scribe;
+
+use std::cmp;
+use models::application::modes::CommandMode;
+use models::application::modes::command::MAX_RESULTS;
+use pad::PadStr;
+use presenters::{buffer_status_line_data, line_count, visible_tokens};
+use rustbox::Color;
+use view::{BufferData, StatusLineData, View};
+use scribe::buffer::{Buffer, Position};
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &CommandMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+
+        // Draw the status line.
+        view.draw_status_line(&vec![
+            StatusLineData {
+                content: " COMMAND ".to_string(),
+                style: None,
+                background_color: Some(Color::White),
+                foreground_color: Some(Color::Black)
+            },
+            buffer_status_line_data(&buf)
+        ]);
+    }
+
+    // Display an empty result set message.
+    if mode.results.is_empty() {
+        view.print(0,
+                   0,
+                   rustbox::RB_NORMAL,
+                   Color::Default,
+                   Color::Default,
+                   &"No matching commands.".pad_to_width(view.width()));
+    }
+
+    // Draw the list of search results, with key bindings right-aligned.
+    for (line, result) in mode.results.iter().enumerate() {
+        let color = if line == mode.results.selected_index() {
+            view.alt_background_color()
+        } else {
+            Color::Default
+        };
+        let binding = match result.binding {
+            Some(ref key) => format!("{} ", key),
+            None => String::new(),
+        };
+        let name_width = view.width().checked_sub(binding.len()).unwrap_or(0);
+        let padded_content = format!("{}{}", result.name.pad_to_width(name_width), binding);
+        view.print(0,
+                   line,
+                   rustbox::RB_NORMAL,
+                   Color::Default,
+                   color,
+                   &padded_content);
+    }
+
+    // Clear any remaining lines in the result display area.
+    for line in cmp::max(mode.results.len(), 1)..MAX_RESULTS {
+        view.print(0,
+                   line,
+                   rustbox::RB_NORMAL,
+                   Color::Default,
+                   Color::Default,
+                   &String::new().pad_to_width(view.width()));
+    }
+
+    // Draw the divider.
+    let line = MAX_RESULTS;
+    let padded_content = mode.input.pad_to_width(view.width());
+    view.print(0,
+               line,
+               rustbox::RB_BOLD,
+               Color::Black,
+               Color::White,
+               &padded_content);
+
+    // Place the cursor on the search input line, right after its contents.
+    view.set_cursor(Some(Position {
+        line: MAX_RESULTS,
+        offset: mode.input.len(),
+    }));
+
+    // Render the changes to the screen.
+    view.present();
+}
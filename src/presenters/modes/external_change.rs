@@ -0,0 +1,88 @@
+extern crate rustbox;
+extern crate scribe;
+
+use helpers::diff::DiffLine;
+use models::application::modes::external_change::ExternalChangeMode;
+use pad::PadStr;
+use presenters::{line_count, visible_tokens};
+use rustbox::Color;
+use scribe::buffer::Buffer;
+use view::{BufferData, StatusLineData, View};
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &ExternalChangeMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    match mode.diff {
+        Some(ref diff) => {
+            // Use the whole screen (save for the status line) to list the
+            // changes that reloading would make, starting at the offset.
+            let lines = diff.iter().skip(mode.line_offset).take(view.height() - 1);
+            for (line, diff_line) in lines.enumerate() {
+                let (content, color) = match *diff_line {
+                    DiffLine::Unchanged(ref content) => (format!("  {}", content), Color::Default),
+                    DiffLine::Added(ref content) => (format!("+ {}", content), Color::Green),
+                    DiffLine::Removed(ref content) => (format!("- {}", content), Color::Red),
+                };
+                view.print(0,
+                           line,
+                           rustbox::RB_NORMAL,
+                           color,
+                           Color::Default,
+                           &content.pad_to_width(view.width()));
+            }
+        }
+        None => {
+            if let Some(buf) = buffer {
+                let line_offset = view.visible_region(buf).line_offset();
+                let visible_range = view.visible_region(buf).visible_range();
+
+                // Get the buffer's tokens and reduce them to the visible set.
+                let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+                // Bundle up the presentable data.
+                let data = BufferData {
+                    tokens: Some(visible_tokens),
+                    cursor: None,
+                    highlight: None,
+                    search_highlights: Vec::new(),
+                    cursors: Vec::new(),
+                    line_count: line_count(&buf.data()),
+                    scrolling_offset: line_offset,
+                };
+
+                // Draw the visible set of tokens to the terminal.
+                view.draw_buffer(&data);
+            }
+        }
+    }
+
+    // There's nothing to edit; hide the cursor.
+    view.set_cursor(None);
+
+    // Draw the status line as a prompt.
+    let diff_action = if mode.diff.is_some() {
+        "hide (d)iff"
+    } else {
+        "(d)iff"
+    };
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " CHANGED ON DISK ".to_string(),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        },
+        StatusLineData {
+            content: format!(" {}: (r)eload, (k)eep buffer, {}",
+                             mode.path.to_string_lossy(),
+                             diff_action),
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ]);
+
+    // Render the changes to the screen.
+    view.present();
+}
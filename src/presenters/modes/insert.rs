@@ -1,13 +1,13 @@
 extern crate scribe;
 extern crate rustbox;
 
-use presenters::{buffer_status_line_data, line_count, visible_tokens};
+use presenters::{buffer_status_line_data, line_count, visible_cursors, visible_tokens};
 use scribe::buffer::{Buffer, Position};
 use rustbox::Color;
 use view::{BufferData, StatusLineData, View};
 use view::scrollable_region::Visibility;
 
-pub fn display(buffer: Option<&mut Buffer>, view: &mut View) {
+pub fn display(buffer: Option<&mut Buffer>, cursors: &[Position], view: &mut View) {
     // Wipe the slate clean.
     view.clear();
 
@@ -37,6 +37,8 @@ pub fn display(buffer: Option<&mut Buffer>, view: &mut View) {
             tokens: Some(visible_tokens),
             cursor: relative_cursor,
             highlight: None,
+            search_highlights: Vec::new(),
+            cursors: visible_cursors(view.visible_region(buf), cursors),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
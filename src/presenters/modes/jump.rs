@@ -25,6 +25,8 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &mut JumpMode, view: &mut View
             tokens: Some(jump_tokens),
             cursor: None,
             highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
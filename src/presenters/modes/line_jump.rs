@@ -36,6 +36,8 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &LineJumpMode, view: &mut View
             tokens: Some(visible_tokens),
             cursor: relative_cursor,
             highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
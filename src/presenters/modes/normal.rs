@@ -3,13 +3,21 @@ extern crate rustbox;
 extern crate git2;
 
 use scribe::buffer::{Buffer, Position};
-use presenters::{buffer_status_line_data, git_status_line_data, line_count, visible_tokens};
+use helpers::search;
+use presenters::{buffer_status_line_data, git_status_line_data, line_count,
+                 search_result_counter, visible_cursors, visible_search_results,
+                 visible_tokens};
 use view::{BufferData, StatusLineData, View};
 use view::scrollable_region::Visibility;
 use rustbox::Color;
 use git2::Repository;
 
-pub fn display(buffer: Option<&mut Buffer>, view: &mut View, repo: &Option<Repository>) {
+pub fn display(buffer: Option<&mut Buffer>,
+               cursors: &[Position],
+               view: &mut View,
+               repo: &Option<Repository>,
+               search_query: &Option<String>,
+               search_regex: bool) {
     // Wipe the slate clean.
     view.clear();
 
@@ -34,11 +42,21 @@ pub fn display(buffer: Option<&mut Buffer>, view: &mut View, repo: &Option<Repos
             _ => None,
         };
 
+        // Find the active search's results, so that we can highlight them.
+        let search_results = match *search_query {
+            Some(ref query) => search::find_all(&buf.data(), query, search_regex),
+            None => Vec::new(),
+        };
+        let search_highlights = visible_search_results(view.visible_region(buf),
+                                                       &search_results);
+
         // Bundle up the presentable data.
         let data = BufferData {
             tokens: Some(visible_tokens),
             cursor: relative_cursor,
             highlight: None,
+            search_highlights: search_highlights,
+            cursors: visible_cursors(view.visible_region(buf), cursors),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
@@ -57,17 +75,28 @@ pub fn display(buffer: Option<&mut Buffer>, view: &mut View, repo: &Option<Repos
         };
 
         // Build the status line mode and buffer title display.
-        let status_line_data = vec![
+        let mut status_line_data = vec![
             StatusLineData {
                 content: " NORMAL ".to_string(),
                 style: None,
                 background_color: bg,
                 foreground_color: fg,
-            },
-            buffer_status_line_data(&buf),
-            git_status_line_data(&repo, &buf.path)
+            }
         ];
 
+        // Show where we are in the search results, if there's an active search.
+        if search_query.as_ref().map(|query| !query.is_empty()).unwrap_or(false) {
+            status_line_data.push(StatusLineData {
+                content: format!(" {} ", search_result_counter(&search_results, &*buf.cursor)),
+                style: None,
+                background_color: Some(Color::Yellow),
+                foreground_color: Some(Color::Black),
+            });
+        }
+
+        status_line_data.push(buffer_status_line_data(&buf));
+        status_line_data.push(git_status_line_data(&repo, &buf.path));
+
         // Draw the status line.
         view.draw_status_line(&status_line_data);
     } else {
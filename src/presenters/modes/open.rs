@@ -26,6 +26,8 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &OpenMode, view: &mut View) {
             tokens: Some(visible_tokens),
             cursor: None,
             highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
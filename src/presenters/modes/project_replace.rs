@@ -0,0 +1,81 @@
+extern crate rustbox;
+
+use models::application::modes::ProjectReplaceMode;
+use pad::PadStr;
+use rustbox::Color;
+use view::{StatusLineData, View};
+
+pub fn display(mode: &ProjectReplaceMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    // Use the whole screen (save for the status line) to list changes,
+    // drawing the page containing the selection.
+    let page_size = view.height() - 1;
+    let selected_index = mode.changes.selected_index();
+    let page_start = selected_index - selected_index % page_size;
+    for (line, (index, change)) in mode.changes
+                                       .iter()
+                                       .enumerate()
+                                       .skip(page_start)
+                                       .take(page_size)
+                                       .enumerate() {
+        let background_color = if index == selected_index {
+            view.alt_background_color()
+        } else {
+            Color::Default
+        };
+        let (marker, foreground_color) = if mode.excluded.contains(&index) {
+            ("[ ]", Color::Default)
+        } else {
+            ("[x]", Color::Green)
+        };
+        let content = format!("{} {}", marker, change);
+        view.print(0,
+                   line,
+                   rustbox::RB_NORMAL,
+                   foreground_color,
+                   background_color,
+                   &content.pad_to_width(view.width()));
+    }
+
+    if mode.changes.is_empty() {
+        view.print(0,
+                   0,
+                   rustbox::RB_NORMAL,
+                   Color::Default,
+                   Color::Default,
+                   &"No matches.".pad_to_width(view.width()));
+    }
+
+    // Draw the status line, warning if files have been left out.
+    let included_count = mode.changes.len() - mode.excluded.len();
+    let (note, note_color) = if mode.truncated {
+        ("; too many matches, so later files are left out", Some(Color::Yellow))
+    } else {
+        ("; space toggles, enter applies", None)
+    };
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " PROJECT REPLACE ".to_string(),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        },
+        StatusLineData {
+            content: format!(" {} of {} changes selected{}",
+                             included_count,
+                             mode.changes.len(),
+                             note),
+            style: None,
+            background_color: note_color,
+            foreground_color: None,
+        }
+    ]);
+
+    // There's no text to edit; hide the cursor.
+    view.set_cursor(None);
+
+    // Render the changes to the screen.
+    view.present();
+}
@@ -0,0 +1,125 @@
+extern crate rustbox;
+extern crate scribe;
+
+use models::application::modes::ProjectSearchMode;
+use models::application::modes::project_search::MAX_RESULTS;
+use pad::PadStr;
+use presenters::{line_count, visible_tokens};
+use rustbox::Color;
+use view::{BufferData, StatusLineData, View};
+use scribe::buffer::{Buffer, Position};
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &ProjectSearchMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+    }
+
+    // Describe the state of the search in the status line.
+    let (status, status_color) = match mode.error {
+        Some(ref error) => (error.clone(), Some(Color::Red)),
+        None => {
+            match mode.results {
+                Some(ref results) => {
+                    match results.len() {
+                        0 => ("No matches.".to_string(), None),
+                        1 => ("1 match".to_string(), None),
+                        count if mode.truncated => {
+                            (format!("{} matches; stopped early, before searching every file",
+                                     count),
+                             Some(Color::Yellow))
+                        }
+                        count => (format!("{} matches", count), None),
+                    }
+                }
+                None => ("Press enter to search.".to_string(), None),
+            }
+        }
+    };
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " PROJECT SEARCH ".to_string(),
+            style: None,
+            background_color: Some(Color::White),
+            foreground_color: Some(Color::Black),
+        },
+        StatusLineData {
+            content: format!(" {}", status),
+            style: None,
+            background_color: status_color,
+            foreground_color: None,
+        }
+    ]);
+
+    // Draw the page of results containing the selection.
+    let mut line = 0;
+    if let Some(ref results) = mode.results {
+        let page_start = results.selected_index() - results.selected_index() % MAX_RESULTS;
+        for (index, result) in results.iter().enumerate().skip(page_start).take(MAX_RESULTS) {
+            let color = if index == results.selected_index() {
+                view.alt_background_color()
+            } else {
+                Color::Default
+            };
+            view.print(0,
+                       line,
+                       rustbox::RB_NORMAL,
+                       Color::Default,
+                       color,
+                       &result.to_string().pad_to_width(view.width()));
+            line += 1;
+        }
+    }
+
+    // Clear any remaining lines in the result display area.
+    for line in line..MAX_RESULTS {
+        view.print(0,
+                   line,
+                   rustbox::RB_NORMAL,
+                   Color::Default,
+                   Color::Default,
+                   &String::new().pad_to_width(view.width()));
+    }
+
+    // Draw the divider.
+    let prompt = if mode.regex {
+        format!("Pattern: {}", mode.input)
+    } else {
+        format!("Search: {}", mode.input)
+    };
+    view.print(0,
+               MAX_RESULTS,
+               rustbox::RB_BOLD,
+               Color::Black,
+               Color::White,
+               &prompt.pad_to_width(view.width()));
+
+    // Place the cursor on the search input line, right after its contents.
+    view.set_cursor(Some(Position {
+        line: MAX_RESULTS,
+        offset: prompt.chars().count(),
+    }));
+
+    // Render the changes to the screen.
+    view.present();
+}
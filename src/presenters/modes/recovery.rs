@@ -0,0 +1,58 @@
+extern crate rustbox;
+extern crate scribe;
+
+use models::application::modes::recovery::RecoveryMode;
+use presenters::{line_count, visible_tokens};
+use rustbox::Color;
+use scribe::buffer::Buffer;
+use view::{BufferData, StatusLineData, View};
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &RecoveryMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+    }
+
+    // There's nothing to edit; hide the cursor.
+    view.set_cursor(None);
+
+    // Draw the status line as a prompt.
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " RECOVERY ".to_string(),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        },
+        StatusLineData {
+            content: format!(" Found unsaved changes to {}: (r)ecover or (d)iscard them?",
+                             mode.path.to_string_lossy()),
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ]);
+
+    // Render the changes to the screen.
+    view.present();
+}
@@ -0,0 +1,71 @@
+extern crate scribe;
+
+use presenters::{line_count, visible_tokens};
+use scribe::buffer::{Buffer, Position};
+use view::scrollable_region::Visibility;
+use view::{BufferData, StatusLineData, View};
+
+pub fn display(buffer: Option<&mut Buffer>, prompt: &str, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // The buffer tracks its cursor absolutely, but the view must display it
+        // relative to any scrolling. Given that, it may also be outside the
+        // visible range, at which point we'll use a None value.
+        let relative_cursor = match view.visible_region(buf)
+                                        .relative_position(buf.cursor.line) {
+            Visibility::Visible(line) => {
+                Some(Position {
+                    line: line,
+                    offset: buf.cursor.offset,
+                })
+            }
+            _ => None,
+        };
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: relative_cursor,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Handle cursor updates.
+        view.set_cursor(data.cursor);
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+
+        // Draw the status line as a prompt for the register (or macro slot) name.
+        let input_prompt = format!("{}: ", prompt);
+        let input_prompt_len = input_prompt.len();
+        view.draw_status_line(&vec![
+            StatusLineData {
+                content: input_prompt,
+                style: None,
+                background_color: None,
+                foreground_color: None,
+            }
+        ]);
+
+        // Move the cursor to the end of the prompt.
+        view.set_cursor(Some(Position {
+            line: view.height() - 1,
+            offset: input_prompt_len,
+        }));
+    }
+
+    // Render the changes to the screen.
+    view.present();
+}
@@ -0,0 +1,67 @@
+extern crate rustbox;
+extern crate scribe;
+
+use models::application::modes::replace_confirm::ReplaceConfirmMode;
+use scribe::buffer::Buffer;
+use presenters::{line_count, relative_range, visible_tokens};
+use view::{BufferData, StatusLineData, View};
+use rustbox::Color;
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &ReplaceConfirmMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Highlight the match we're asking about, relative to the scrolled buffer.
+        let relative_highlight = match mode.current_match {
+            Some((ref range, _)) => Some(relative_range(view.visible_region(buf), range)),
+            None => None,
+        };
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: relative_highlight,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+    }
+
+    // Hide the cursor; the highlighted match stands in for it.
+    view.set_cursor(None);
+
+    // Draw the status line as a prompt, showing the current match's replacement.
+    let replacement = match mode.current_match {
+        Some((_, ref replacement)) => replacement.clone(),
+        None => mode.replacement.clone(),
+    };
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " REPLACE ".to_string(),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        },
+        StatusLineData {
+            content: format!(" Replace with {}? (y)es, (n)o, (a)ll, (q)uit", replacement),
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ]);
+
+    // Render the changes to the screen.
+    view.present();
+}
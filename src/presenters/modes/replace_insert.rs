@@ -0,0 +1,79 @@
+extern crate rustbox;
+extern crate scribe;
+
+use scribe::buffer::{Buffer, Position};
+use presenters::{line_count, visible_tokens};
+use view::{BufferData, StatusLineData, View};
+use models::application::modes::replace_insert::ReplaceInsertMode;
+use rustbox::Color;
+
+/// Displays the replace prompt, titled to describe its scope (e.g. "Replace in project").
+pub fn display(buffer: Option<&mut Buffer>,
+               mode: &ReplaceInsertMode,
+               title: &str,
+               view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+    }
+
+    // Draw the status line as a prompt for whichever value is being entered.
+    let label = if mode.regex {
+        format!("{} (regex)", title)
+    } else {
+        title.to_string()
+    };
+    let prompt = match mode.replacement {
+        Some(ref replacement) => format!("{} {} with: {}", label, mode.query, replacement),
+        None => format!("{}: {}", label, mode.query),
+    };
+    let prompt_len = prompt.chars().count();
+    let mut status_line_data = vec![
+        StatusLineData {
+            content: prompt,
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ];
+
+    // Explain why the query couldn't be used, if necessary.
+    if let Some(ref error) = mode.error {
+        status_line_data.push(StatusLineData {
+            content: format!(" {} ", error),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        });
+    }
+    view.draw_status_line(&status_line_data);
+
+    // Move the cursor to the end of the input.
+    view.set_cursor(Some(Position {
+        line: view.height() - 1,
+        offset: prompt_len,
+    }));
+
+    // Render the changes to the screen.
+    view.present();
+}
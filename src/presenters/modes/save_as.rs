@@ -0,0 +1,78 @@
+extern crate rustbox;
+extern crate scribe;
+
+use scribe::buffer::{Buffer, Position};
+use presenters::{line_count, visible_tokens};
+use view::{BufferData, StatusLineData, View};
+use models::application::modes::save_as::SaveAsMode;
+use rustbox::Color;
+
+pub fn display(buffer: Option<&mut Buffer>, mode: &SaveAsMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    if let Some(buf) = buffer {
+        let line_offset = view.visible_region(buf).line_offset();
+        let visible_range = view.visible_region(buf).visible_range();
+
+        // Get the buffer's tokens and reduce them to the visible set.
+        let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
+
+        // Bundle up the presentable data.
+        let data = BufferData {
+            tokens: Some(visible_tokens),
+            cursor: None,
+            highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
+            line_count: line_count(&buf.data()),
+            scrolling_offset: line_offset,
+        };
+
+        // Draw the visible set of tokens to the terminal.
+        view.draw_buffer(&data);
+    }
+
+    // Draw the status line as a path prompt.
+    let prompt = format!("Save as: {}", mode.input);
+    let prompt_len = prompt.chars().count();
+    let mut status_line_data = vec![
+        StatusLineData {
+            content: prompt,
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ];
+
+    // Make sure that existing files aren't overwritten by accident.
+    if mode.confirming_overwrite {
+        status_line_data.push(StatusLineData {
+            content: " That file already exists; press enter again to overwrite it. "
+                         .to_string(),
+            style: None,
+            background_color: Some(Color::Yellow),
+            foreground_color: Some(Color::Black),
+        });
+    }
+
+    // Explain why the buffer couldn't be saved, if necessary.
+    if let Some(ref error) = mode.error {
+        status_line_data.push(StatusLineData {
+            content: format!(" {} ", error),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        });
+    }
+    view.draw_status_line(&status_line_data);
+
+    // Move the cursor to the end of the path input.
+    view.set_cursor(Some(Position {
+        line: view.height() - 1,
+        offset: prompt_len,
+    }));
+
+    // Render the changes to the screen.
+    view.present();
+}
@@ -1,9 +1,12 @@
+extern crate rustbox;
 extern crate scribe;
 
 use scribe::buffer::{Buffer, Position};
-use presenters::{line_count, visible_tokens};
+use helpers::search;
+use presenters::{line_count, relative_range, visible_search_results, visible_tokens};
 use view::{BufferData, StatusLineData, View};
 use models::application::modes::search_insert::SearchInsertMode;
+use rustbox::Color;
 
 pub fn display(buffer: Option<&mut Buffer>, mode: &SearchInsertMode, view: &mut View) {
     // Wipe the slate clean.
@@ -16,11 +19,23 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &SearchInsertMode, view: &mut
         // Get the buffer's tokens and reduce them to the visible set.
         let visible_tokens = visible_tokens(&buf.tokens(), visible_range);
 
+        // Highlight the results as the query is typed,
+        // emphasizing the one the cursor has moved to.
+        let search_results = search::find_all(&buf.data(), &mode.input, mode.regex);
+        let search_highlights = visible_search_results(view.visible_region(buf),
+                                                       &search_results);
+        let current_highlight = match mode.current_result {
+            Some(ref range) => Some(relative_range(view.visible_region(buf), range)),
+            None => None,
+        };
+
         // Bundle up the presentable data.
         let data = BufferData {
             tokens: Some(visible_tokens),
             cursor: None,
-            highlight: None,
+            highlight: current_highlight,
+            search_highlights: search_highlights,
+            cursors: Vec::new(),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
@@ -30,16 +45,31 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &SearchInsertMode, view: &mut
     }
 
     // Draw the status line as a search prompt.
-    let search_prompt = format!("Search: {}", mode.input);
+    let search_prompt = if mode.regex {
+        format!("Pattern search: {}", mode.input)
+    } else {
+        format!("Search: {}", mode.input)
+    };
     let search_prompt_len = search_prompt.len();
-    view.draw_status_line(&vec![
+    let mut status_line_data = vec![
         StatusLineData {
             content: search_prompt,
             style: None,
             background_color: None,
             foreground_color: None,
         }
-    ]);
+    ];
+
+    // Explain why the pattern couldn't be used, if necessary.
+    if let Some(ref error) = mode.error {
+        status_line_data.push(StatusLineData {
+            content: format!(" {} ", error),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        });
+    }
+    view.draw_status_line(&status_line_data);
 
     // Move the cursor to the end of the search query input.
     view.set_cursor(Some(Position {
@@ -3,12 +3,16 @@ extern crate scribe;
 
 use models::application::modes::select::SelectMode;
 use scribe::buffer::{Buffer, Position, Range};
-use presenters::{buffer_status_line_data, line_count, relative_range, visible_tokens};
+use presenters::{buffer_status_line_data, line_count, relative_range, visible_cursors,
+                 visible_tokens};
 use view::{BufferData, StatusLineData, View};
 use view::scrollable_region::Visibility;
 use rustbox::Color;
 
-pub fn display(buffer: Option<&mut Buffer>, mode: &SelectMode, view: &mut View) {
+pub fn display(buffer: Option<&mut Buffer>,
+               mode: &SelectMode,
+               cursors: &[Position],
+               view: &mut View) {
     // Wipe the slate clean.
     view.clear();
 
@@ -45,6 +49,8 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &SelectMode, view: &mut View)
             tokens: Some(visible_tokens),
             cursor: relative_cursor,
             highlight: Some(relative_highlight),
+            search_highlights: Vec::new(),
+            cursors: visible_cursors(view.visible_region(buf), cursors),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
@@ -45,6 +45,8 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &SelectLineMode, view: &mut Vi
             tokens: Some(visible_tokens),
             cursor: relative_cursor,
             highlight: Some(relative_highlight),
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
@@ -25,6 +25,8 @@ pub fn display(buffer: Option<&mut Buffer>, mode: &SymbolJumpMode, view: &mut Vi
             tokens: Some(visible_tokens),
             cursor: None,
             highlight: None,
+            search_highlights: Vec::new(),
+            cursors: Vec::new(),
             line_count: line_count(&buf.data()),
             scrolling_offset: line_offset,
         };
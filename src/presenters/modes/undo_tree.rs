@@ -0,0 +1,88 @@
+extern crate rustbox;
+
+use models::application::modes::undo_tree::{UndoTreeEntry, UndoTreeMode};
+use models::application::undo_tree;
+use pad::PadStr;
+use rustbox::Color;
+use view::{StatusLineData, View};
+
+pub fn display(mode: &UndoTreeMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    // Draw the page of states containing the selection,
+    // with the buffer's current state in bold.
+    let page_size = view.height() - 1;
+    let now = undo_tree::now();
+    let selected_index = mode.states.selected_index();
+    let page_start = selected_index - selected_index % page_size;
+    for (line, (index, entry)) in mode.states
+                                      .iter()
+                                      .enumerate()
+                                      .skip(page_start)
+                                      .take(page_size)
+                                      .enumerate() {
+        let background_color = if index == selected_index {
+            view.alt_background_color()
+        } else {
+            Color::Default
+        };
+        let style = if entry.current {
+            rustbox::RB_BOLD
+        } else {
+            rustbox::RB_NORMAL
+        };
+        view.print(0,
+                   line,
+                   style,
+                   Color::Default,
+                   background_color,
+                   &describe(entry, now).pad_to_width(view.width()));
+    }
+
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " UNDO TREE ".to_string(),
+            style: None,
+            background_color: Some(Color::White),
+            foreground_color: Some(Color::Black),
+        },
+        StatusLineData {
+            content: format!(" {} states", mode.states.len()),
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ]);
+
+    // There's no text to edit; hide the cursor.
+    view.set_cursor(None);
+
+    // Render the changes to the screen.
+    view.present();
+}
+
+/// Describes a state by its number, age, and where it came from,
+/// e.g. "  12  3m ago  (branched from 9)".
+fn describe(entry: &UndoTreeEntry, now: u64) -> String {
+    let origin = match entry.parent {
+        Some(parent) if entry.branch => format!("  (branched from {})", parent),
+        Some(_) => String::new(),
+        None => "  (original)".to_string(),
+    };
+
+    format!("{:>4}  {}{}", entry.index, age(now.saturating_sub(entry.timestamp)), origin)
+}
+
+/// Formats a duration in seconds using its largest unit, e.g. "3h ago".
+fn age(seconds: u64) -> String {
+    if seconds < 60 {
+        format!("{}s ago", seconds)
+    } else if seconds < 60 * 60 {
+        format!("{}m ago", seconds / 60)
+    } else if seconds < 60 * 60 * 24 {
+        format!("{}h ago", seconds / (60 * 60))
+    } else {
+        format!("{}d ago", seconds / (60 * 60 * 24))
+    }
+}
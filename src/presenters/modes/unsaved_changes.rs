@@ -0,0 +1,47 @@
+extern crate rustbox;
+
+use models::application::modes::unsaved_changes::{PendingAction, UnsavedChangesMode};
+use pad::PadStr;
+use rustbox::Color;
+use view::{StatusLineData, View};
+
+pub fn display(mode: &UnsavedChangesMode, view: &mut View) {
+    // Wipe the slate clean.
+    view.clear();
+
+    // List the buffers with unsaved changes.
+    for (line, entry) in mode.buffers.iter().take(view.height() - 1).enumerate() {
+        view.print(0,
+                   line,
+                   rustbox::RB_BOLD,
+                   Color::Default,
+                   Color::Default,
+                   &format!("{}*", entry.title).pad_to_width(view.width()));
+    }
+
+    // Draw the status line as a prompt.
+    let action = match mode.action {
+        PendingAction::CloseBuffer => "closing",
+        PendingAction::Exit => "exiting",
+    };
+    view.draw_status_line(&vec![
+        StatusLineData {
+            content: " UNSAVED CHANGES ".to_string(),
+            style: None,
+            background_color: Some(Color::Red),
+            foreground_color: Some(Color::White),
+        },
+        StatusLineData {
+            content: format!(" Before {}: (s)ave, (d)iscard, or (c)ancel?", action),
+            style: None,
+            background_color: None,
+            foreground_color: None,
+        }
+    ]);
+
+    // There's no text to edit; hide the cursor.
+    view.set_cursor(None);
+
+    // Render the changes to the screen.
+    view.present();
+}
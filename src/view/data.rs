@@ -5,6 +5,10 @@ pub struct BufferData {
     pub tokens: Option<Vec<Token>>,
     pub cursor: Option<Position>,
     pub highlight: Option<Range>,
+    pub search_highlights: Vec<Range>,
+
+    /// Cursors besides the primary one, which are drawn as reversed characters.
+    pub cursors: Vec<Position>,
     pub line_count: usize,
     pub scrolling_offset: usize,
 }
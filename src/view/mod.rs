@@ -18,8 +18,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use self::scrollable_region::ScrollableRegion;
 
-const LINE_LENGTH_GUIDE_OFFSET: usize = 80;
-
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Theme {
     Dark,
     Light,
@@ -27,6 +26,9 @@ pub enum Theme {
 
 pub struct View {
     pub theme: Theme,
+
+    /// The column at which the line length guide is drawn, if any.
+    pub line_length_guide: Option<usize>,
     terminal: Rc<RefCell<Terminal>>,
     scrollable_regions: HashMap<usize, ScrollableRegion>,
 }
@@ -37,6 +39,7 @@ impl View {
 
         View {
             theme: Theme::Dark,
+            line_length_guide: None,
             terminal: terminal,
             scrollable_regions: HashMap::new(),
         }
@@ -89,6 +92,16 @@ impl View {
                     }
                     None => (rustbox::RB_NORMAL, token_color),
                 };
+                let style = if data.cursors.contains(&current_position) {
+                    rustbox::RB_REVERSE
+                } else {
+                    style
+                };
+
+                // Search results are drawn underneath any other highlight.
+                let search_result = data.search_highlights
+                                        .iter()
+                                        .any(|range| range.includes(&current_position));
 
                 let background_color = match data.cursor {
                     Some(cursor) => {
@@ -100,6 +113,11 @@ impl View {
                     }
                     None => Color::Default,
                 };
+                let (color, background_color) = if search_result && style == rustbox::RB_NORMAL {
+                    (Color::Black, Color::Yellow)
+                } else {
+                    (color, background_color)
+                };
 
                 if character == '\n' {
                     // Print the rest of the line highlight.
@@ -120,14 +138,25 @@ impl View {
                     }
 
                     // Print the length guide for this line.
-                    let absolute_length_guide_offset =
-                      gutter_width + LINE_LENGTH_GUIDE_OFFSET;
-                    if offset <= absolute_length_guide_offset {
-                        self.print_char(absolute_length_guide_offset,
+                    if let Some(line_length_guide) = self.line_length_guide {
+                        let absolute_length_guide_offset = gutter_width + line_length_guide;
+                        if offset <= absolute_length_guide_offset {
+                            self.print_char(absolute_length_guide_offset,
+                                            line,
+                                            rustbox::RB_NORMAL,
+                                            Color::Default,
+                                            self.alt_background_color(),
+                                            ' ');
+                        }
+                    }
+
+                    // Draw additional cursors at the end of the line over the newline.
+                    if data.cursors.contains(&current_position) {
+                        self.print_char(offset,
                                         line,
-                                        rustbox::RB_NORMAL,
+                                        rustbox::RB_REVERSE,
+                                        Color::Default,
                                         Color::Default,
-                                        self.alt_background_color(),
                                         ' ');
                     }
 
@@ -160,6 +189,15 @@ impl View {
             }
             None => (),
         }
+
+        // Draw an additional cursor at the end of the buffer.
+        let end_position = Position {
+            line: line,
+            offset: offset - gutter_width,
+        };
+        if data.cursors.contains(&end_position) {
+            self.print_char(offset, line, rustbox::RB_REVERSE, Color::Default, Color::Default, ' ');
+        }
     }
 
     pub fn draw_status_line(&self, data: &Vec<StatusLineData>) {
@@ -276,6 +314,10 @@ impl View {
         self.get_region(buffer).scroll_to_center(buffer.cursor.line);
     }
 
+    pub fn scroll_to(&mut self, buffer: &Buffer, line_offset: usize) {
+        self.get_region(buffer).scroll_to(line_offset);
+    }
+
     pub fn scroll_up(&mut self, buffer: &Buffer, amount: usize) {
         self.get_region(buffer).scroll_up(amount);
     }
@@ -334,6 +376,10 @@ impl View {
         self.terminal.borrow().present()
     }
 
+    pub fn write_raw(&self, output: &str) {
+        self.terminal.borrow().write_raw(output);
+    }
+
     pub fn print(&self, x: usize, y: usize, style: Style, fg: Color, bg: Color, s: &str) {
         self.terminal.borrow().print(x, y, style, fg, bg, s);
     }
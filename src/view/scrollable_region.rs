@@ -47,6 +47,12 @@ impl ScrollableRegion {
         self.line_offset = line.checked_sub(self.height() / 2).unwrap_or(0);
     }
 
+    /// Moves the line offset to the specified value, e.g. to
+    /// restore a previously recorded scrolling position.
+    pub fn scroll_to(&mut self, line_offset: usize) {
+        self.line_offset = line_offset;
+    }
+
     /// Converts an absolutely positioned line number into
     /// one relative to the scrollable regions visible range.
     /// The visibility type is based on whether or not the line
@@ -117,6 +123,15 @@ mod tests {
         assert_eq!(range.end(), 19);
     }
 
+    #[test]
+    fn scroll_to_sets_the_line_offset() {
+        let terminal = Rc::new(RefCell::new(Terminal::new()));
+        let mut region = ScrollableRegion::new(terminal);
+        region.scroll_down(10);
+        region.scroll_to(3);
+        assert_eq!(region.line_offset(), 3);
+    }
+
     #[test]
     fn scroll_into_view_advances_region_if_line_after_current_range() {
         let terminal = Rc::new(RefCell::new(Terminal::new()));
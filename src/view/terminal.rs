@@ -3,6 +3,7 @@ extern crate scribe;
 
 use std::error::Error;
 use std::default::Default;
+use std::io::{self, Write};
 use scribe::buffer::Position;
 use rustbox::{Color, InitOptions, RustBox, Style};
 
@@ -55,6 +56,19 @@ impl Terminal {
         }
     }
 
+    /// Writes raw output (e.g. escape sequences) straight to the terminal.
+    /// Only call this once a frame has been presented, so that it doesn't
+    /// end up in the middle of rustbox's own output.
+    pub fn write_raw(&self, output: &str) {
+        match self.terminal {
+            Some(_) => {
+                let mut stdout = io::stdout();
+                let _ = stdout.write_all(output.as_bytes()).and_then(|_| stdout.flush());
+            }
+            None => (),
+        }
+    }
+
     pub fn width(&self) -> usize {
         match self.terminal {
             Some(ref t) => t.width(),